@@ -0,0 +1,123 @@
+use crate::attr::{Attribute, ClassName};
+
+// `class` attribute built up from conditional parts, where repeated classes are only kept once.
+pub struct ClassList {
+    classes : Vec<String>,
+}
+impl ClassList {
+    pub fn new() -> ClassList {
+        ClassList {
+            classes : Vec::new(),
+        }
+    }
+
+    pub fn with_class<C>(&mut self, item : C)
+        where C : ClassItem {
+        item.add_to(self);
+    }
+
+    fn push(&mut self, classes : &str) {
+        for class in classes.split_whitespace() {
+            if !self.classes.iter().any(|existing| existing == class) {
+                self.classes.push(String::from(class));
+            }
+        }
+    }
+}
+impl ToString for ClassList {
+    fn to_string(&self) -> String {
+        format!("class=\"{}\"", self.value())
+    }
+}
+impl Attribute for ClassList {
+    fn name(&self) -> String {
+        String::from("class")
+    }
+
+    fn value(&self) -> String {
+        self.classes.join(" ")
+    }
+}
+impl<C : ClassItem> FromIterator<C> for ClassList {
+    fn from_iter<I>(iter : I) -> ClassList
+        where I : IntoIterator<Item = C> {
+        let mut list = ClassList::new();
+        for item in iter {
+            list.with_class(item);
+        }
+        list
+    }
+}
+
+// Anything which can be added to a `ClassList`. Any iterator of these can be collected into a
+// `ClassList`, which is itself one.
+pub trait ClassItem {
+    fn add_to(self, list : &mut ClassList);
+}
+impl ClassItem for &str {
+    fn add_to(self, list : &mut ClassList) {
+        list.push(self);
+    }
+}
+impl ClassItem for &String {
+    fn add_to(self, list : &mut ClassList) {
+        list.push(self);
+    }
+}
+impl ClassItem for String {
+    fn add_to(self, list : &mut ClassList) {
+        list.push(&self);
+    }
+}
+impl ClassItem for ClassName {
+    fn add_to(self, list : &mut ClassList) {
+        list.push(self.0);
+    }
+}
+impl ClassItem for ClassList {
+    fn add_to(self, list : &mut ClassList) {
+        for class in self.classes {
+            list.push(&class);
+        }
+    }
+}
+impl<C : ClassItem> ClassItem for Option<C> {
+    fn add_to(self, list : &mut ClassList) {
+        if let Some(item) = self {
+            item.add_to(list);
+        }
+    }
+}
+// Only added when the condition is true, as in `("active", is_active)`.
+impl<C : ClassItem> ClassItem for (C, bool) {
+    fn add_to(self, list : &mut ClassList) {
+        if self.1 {
+            self.0.add_to(list);
+        }
+    }
+}
+impl<C : ClassItem> ClassItem for Vec<C> {
+    fn add_to(self, list : &mut ClassList) {
+        for item in self {
+            item.add_to(list);
+        }
+    }
+}
+impl<C : ClassItem, const N : usize> ClassItem for [C; N] {
+    fn add_to(self, list : &mut ClassList) {
+        for item in self {
+            item.add_to(list);
+        }
+    }
+}
+
+#[macro_export]
+macro_rules! classes {
+    ($($a:expr),* $(,)?) => {
+        {
+            let mut list = ClassList::new();
+            $(list.with_class($a);)*
+            list
+        }
+    }
+}
@@ -0,0 +1,147 @@
+use crate::css::{AnimationName, CssProp, CssRule, CssValue, FontFamily, Style, Stylesheet, Targets, animation_name, custom_prop, font_family};
+
+// `@keyframes` rule, referenced through `Keyframes::name` with `animation_name_of`, which only
+// takes the name of a defined animation.
+pub struct Keyframes {
+    name : String,
+    frames : Vec<(String, Style)>,
+}
+pub fn keyframes(name : &str) -> Keyframes {
+    Keyframes {
+        name : String::from(name),
+        frames : Vec::new(),
+    }
+}
+impl Keyframes {
+    // Adds a keyframe, where `selector` is `from`, `to` or a percentage such as `50%`.
+    pub fn frame(mut self, selector : &str, style : Style) -> Keyframes {
+        self.frames.push((String::from(selector), style));
+        self
+    }
+
+    pub fn name(&self) -> KeyframesName {
+        KeyframesName(self.name.clone())
+    }
+
+    fn rendered(&self, targets : &Targets) -> String {
+        let frames : String =
+            self.frames
+            .iter()
+            .map(|(selector, style)| format!("{} {{{}}}", selector, style.render(targets)))
+            .collect();
+
+        format!("@keyframes {} {{{}}}", self.name, frames)
+    }
+}
+impl ToString for Keyframes {
+    fn to_string(&self) -> String {
+        self.rendered(&Targets::new())
+    }
+}
+impl CssRule for Keyframes {
+    fn render(&self, stylesheet : &Stylesheet) -> String {
+        self.rendered(stylesheet.targets())
+    }
+}
+
+// Name of a `Keyframes`, which can't be made from a string.
+pub struct KeyframesName(String);
+impl ToString for KeyframesName {
+    fn to_string(&self) -> String {
+        self.0.clone()
+    }
+}
+impl CssValue for KeyframesName {}
+
+// `animation-name` of a defined animation. Unlike `animation_name`, which takes any value, a typo
+// in the name here fails to compile.
+pub fn animation_name_of(name : KeyframesName) -> AnimationName {
+    animation_name(name)
+}
+
+// `@font-face` rule, referenced through `FontFace::family` with `font_family_of`, which only takes
+// the family of a defined font.
+pub struct FontFace {
+    family : String,
+    sources : Vec<String>,
+    descriptors : Style,
+}
+pub fn font_face(family : &str) -> FontFace {
+    FontFace {
+        family : String::from(family),
+        sources : Vec::new(),
+        descriptors : Style::new(),
+    }
+}
+impl FontFace {
+    // Adds a font file, such as `src("/fonts/inter.woff2", "woff2")`.
+    pub fn src(mut self, url : &str, format : &str) -> FontFace {
+        self.sources.push(format!("url(\"{}\") format(\"{}\")", url, format));
+        self
+    }
+
+    // Adds a locally installed font as a source.
+    pub fn local(mut self, name : &str) -> FontFace {
+        self.sources.push(format!("local(\"{}\")", name));
+        self
+    }
+
+    // Adds a descriptor which shares its name with a property, such as `font_weight("100 900")`.
+    pub fn descriptor<P : 'static>(mut self, descriptor : P) -> FontFace
+        where P : CssProp {
+        self.descriptors.with_prop(descriptor);
+        self
+    }
+
+    pub fn display(self, value : &str) -> FontFace {
+        self.descriptor(custom_prop("font-display", value))
+    }
+
+    pub fn unicode_range(self, value : &str) -> FontFace {
+        self.descriptor(custom_prop("unicode-range", value))
+    }
+
+    pub fn family(&self) -> FontFaceName {
+        FontFaceName {
+            family : self.family.clone(),
+            fallbacks : Vec::new(),
+        }
+    }
+}
+impl ToString for FontFace {
+    fn to_string(&self) -> String {
+        let mut descriptors = format!("font-family: \"{}\";", self.family);
+        if !self.sources.is_empty() {
+            descriptors.push_str(&format!("src: {};", self.sources.join(", ")));
+        }
+        descriptors.push_str(&self.descriptors.to_string());
+
+        format!("@font-face {{{}}}", descriptors)
+    }
+}
+impl CssRule for FontFace {}
+
+// Family name of a `FontFace`, which can be followed by fallbacks with `FontFaceName::or`.
+pub struct FontFaceName {
+    family : String,
+    fallbacks : Vec<String>,
+}
+impl FontFaceName {
+    pub fn or(mut self, fallback : &str) -> FontFaceName {
+        self.fallbacks.push(String::from(fallback));
+        self
+    }
+}
+impl ToString for FontFaceName {
+    fn to_string(&self) -> String {
+        let mut families = vec![format!("\"{}\"", self.family)];
+        families.extend(self.fallbacks.iter().cloned());
+        families.join(", ")
+    }
+}
+impl CssValue for FontFaceName {}
+
+// `font-family` of a defined font, with any fallbacks given to `FontFaceName::or`.
+pub fn font_family_of(name : FontFaceName) -> FontFamily {
+    font_family(name)
+}
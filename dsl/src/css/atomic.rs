@@ -0,0 +1,170 @@
+use std::collections::HashMap;
+
+use crate::css::{CssProp, Style, Stylesheet, Variant, Condition, rule};
+use crate::css::hoist::add_class;
+use crate::css::parse::strip_important;
+use crate::css::responsive::min_width;
+use crate::css::shorthand::{resets, shorthand_depth};
+use crate::nodes::{Node, for_each_element_mut};
+
+// Single declaration under the conditions of the variants it was nested in.
+struct Declaration {
+    conditions : Vec<Condition>,
+    prop : Box<dyn CssProp>,
+}
+impl Declaration {
+    fn conditions_key(&self) -> String {
+        let mut key = String::new();
+        for condition in &self.conditions {
+            match condition {
+                Condition::Selector(selector) => key.push_str(selector),
+                Condition::Media(query) => key.push_str(&format!("@media {}", query)),
+            }
+            key.push('|');
+        }
+        key
+    }
+
+    // Plain declarations come first, then those under pseudo-classes, and then those under media
+    // queries, so that each can override the last as it would inline.
+    fn precedence(&self) -> usize {
+        if self.conditions.is_empty() {
+            0
+        }
+        else if self.conditions.iter().all(|condition| matches!(condition, Condition::Selector(_))) {
+            1
+        }
+        else {
+            2
+        }
+    }
+
+    // Widest `min-width` of the media queries the declaration is under, or `0` for none.
+    fn min_width(&self) -> f64 {
+        self.conditions
+        .iter()
+        .filter_map(|condition| match condition {
+            Condition::Media(query) => min_width(query),
+            _ => None,
+        })
+        .fold(0.0, f64::max)
+    }
+
+    fn into_rule(self, class : &str) -> crate::css::Rule {
+        let mut style = Style::new();
+        style.props.push(self.prop);
+        for condition in self.conditions.into_iter().rev() {
+            let mut outer = Style::new();
+            outer.variants.push(Variant { condition, style });
+            style = outer;
+        }
+        rule(&format!(".{}", class), style)
+    }
+}
+
+// Replaces every inline style in the tree with atomic classes, one for each distinct declaration,
+// returning the stylesheet which defines them.
+pub fn extract_atomic(root : &mut dyn Node) -> Stylesheet {
+    let mut classes : HashMap<String, String> = HashMap::new();
+    let mut rules : [Vec<(String, Declaration)>; 3] = [Vec::new(), Vec::new(), Vec::new()];
+
+    for_each_element_mut(root, &mut |element| {
+        let style = match element.css_props_mut().take() {
+            Some(style) => style,
+            None => return,
+        };
+
+        let mut declarations = Vec::new();
+        flatten(style, &mut Vec::new(), &mut declarations);
+
+        // As in `Style::normalize`, a later declaration of a property, or a later shorthand
+        // resetting it, overrides an earlier one unless only the earlier one is `!important`, in
+        // which case the later one is dropped. The rules are ordered with shorthands first below,
+        // so whatever is left can't be overridden by a shorthand the element doesn't use after it.
+        let keys : Vec<String> = declarations.iter().map(|declaration| declaration.conditions_key()).collect();
+        let names : Vec<String> = declarations.iter().map(|declaration| declaration.prop.name()).collect();
+        let important : Vec<bool> = declarations.iter().map(|declaration| strip_important(&declaration.prop.value()).1).collect();
+        let applies : Vec<bool> =
+            (0..declarations.len())
+            .map(|index| {
+                let overridden = (index + 1..declarations.len()).any(|later| {
+                    keys[later] == keys[index]
+                    && (important[later] || !important[index])
+                    && (names[later] == names[index] || resets(&names[later], &names[index]))
+                });
+                let outranked = !important[index] && (0..index).any(|earlier| {
+                    keys[earlier] == keys[index] && important[earlier] && names[earlier] == names[index]
+                });
+                !overridden && !outranked
+            })
+            .collect();
+
+        let mut element_classes = Vec::new();
+        for (declaration, applies) in declarations.into_iter().zip(applies) {
+            if !applies {
+                continue;
+            }
+
+            let key = format!("{}{}", declaration.conditions_key(), declaration.prop.to_string());
+            let class = match classes.get(&key) {
+                Some(class) => class.clone(),
+                None => {
+                    let class = format!("a{}", base36(classes.len()));
+                    classes.insert(key, class.clone());
+                    rules[declaration.precedence()].push((class.clone(), declaration));
+                    class
+                },
+            };
+            element_classes.push(class);
+        }
+
+        if !element_classes.is_empty() {
+            add_class(element, &element_classes.join(" "));
+        }
+    });
+
+    // Rules of the same precedence share a specificity, so a shorthand has to come before the
+    // longhands it resets for `margin: 0; margin-top: 4px` to keep its top margin. Media rules
+    // also go in order of their breakpoint, so the widest which matches wins whichever element
+    // first used it.
+    for bucket in &mut rules {
+        bucket.sort_by(|(_, a), (_, b)| {
+            a.min_width().total_cmp(&b.min_width()).then(shorthand_depth(&a.prop.name()).cmp(&shorthand_depth(&b.prop.name())))
+        });
+    }
+
+    let mut stylesheet = Stylesheet::new();
+    for (class, declaration) in rules.into_iter().flatten() {
+        stylesheet.with_rule(declaration.into_rule(&class));
+    }
+    stylesheet
+}
+
+fn flatten(style : Style, conditions : &mut Vec<Condition>, declarations : &mut Vec<Declaration>) {
+    for prop in style.props {
+        declarations.push(Declaration {
+            conditions : conditions.clone(),
+            prop,
+        });
+    }
+
+    for variant in style.variants {
+        conditions.push(variant.condition);
+        flatten(variant.style, conditions, declarations);
+        conditions.pop();
+    }
+}
+
+fn base36(mut number : usize) -> String {
+    let digits = b"0123456789abcdefghijklmnopqrstuvwxyz";
+    let mut string = Vec::new();
+    loop {
+        string.push(digits[number % 36]);
+        number /= 36;
+        if number == 0 {
+            break;
+        }
+    }
+    string.reverse();
+    String::from_utf8(string).unwrap()
+}
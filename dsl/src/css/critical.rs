@@ -0,0 +1,161 @@
+use crate::attr;
+use crate::css::{Stylesheet, split_selectors};
+use crate::css::parse::{comment_end, string_end};
+use crate::css::stylesheet::append_to_head;
+use crate::nodes::{AttributableNode, Link, Node, ParentNode, Selector, StyleElement, Text};
+
+// At-rules containing style rules, which are split like a stylesheet of their own.
+const GROUPING_RULES : &[&str] = &["media", "supports", "container", "layer", "scope", "starting-style"];
+
+// Stylesheet split by whether its rules apply to a tree. `used` holds the rules matching at least
+// one element, with unmatched selectors removed from their lists, and `unused` everything else.
+// At-rules such as `@keyframes` and `@font-face`, and rules whose selector can't be parsed, are
+// always kept in `used`.
+pub struct CriticalCss {
+    pub used : String,
+    pub unused : String,
+}
+
+// Splits a stylesheet, either built with `Stylesheet` or read from a file, by matching its
+// selectors against the tree.
+pub fn critical_css(css : &str, root : &dyn Node) -> CriticalCss {
+    let mut critical = CriticalCss {
+        used : String::new(),
+        unused : String::new(),
+    };
+
+    for (prelude, block) in statements(css) {
+        let block = match block {
+            Some(block) => block,
+            None => {
+                critical.used.push_str(&format!("{};", prelude));
+                continue;
+            },
+        };
+
+        if let Some(at_rule) = prelude.strip_prefix('@') {
+            let name = at_rule.split(|c : char| c.is_whitespace() || c == '(').next().unwrap_or("");
+            if GROUPING_RULES.contains(&name.to_ascii_lowercase().as_str()) {
+                let nested = critical_css(block, root);
+                if !nested.used.is_empty() {
+                    critical.used.push_str(&format!("{} {{{}}}", prelude, nested.used));
+                }
+                if !nested.unused.is_empty() {
+                    critical.unused.push_str(&format!("{} {{{}}}", prelude, nested.unused));
+                }
+            }
+            else {
+                critical.used.push_str(&format!("{} {{{}}}", prelude, block));
+            }
+            continue;
+        }
+
+        let (used, unused) : (Vec<&str>, Vec<&str>) =
+            split_selectors(prelude)
+            .into_iter()
+            .partition(|selector| match Selector::parse(selector) {
+                Ok(selector) => selector.may_match_any(root),
+                Err(_) => true,
+            });
+        if !used.is_empty() {
+            critical.used.push_str(&format!("{} {{{}}}", used.join(", "), block));
+        }
+        if !unused.is_empty() {
+            critical.unused.push_str(&format!("{} {{{}}}", unused.join(", "), block));
+        }
+    }
+
+    critical
+}
+
+impl CriticalCss {
+    // Appends the used rules as a `<style>` element to the first `<head>` of the tree, followed by
+    // a `<link>` loading the unused rules from `deferred_href` without blocking rendering, if
+    // given. Returns whether there was a `<head>`.
+    pub fn insert_into_head(&self, root : &mut dyn Node, deferred_href : Option<&str>) -> bool {
+        let mut style = StyleElement::new();
+        style.child(Text::new(&self.used));
+        let mut nodes : Vec<Box<dyn Node>> = vec![Box::new(style)];
+
+        if let Some(href) = deferred_href {
+            // Stylesheets for print don't block rendering, and switch to all media once loaded.
+            let mut link = Link::new();
+            link.attribute(attr::rel("stylesheet"));
+            link.attribute(attr::href(href));
+            link.attribute(attr::media("print"));
+            link.attribute(attr::onload("this.media='all'"));
+            nodes.push(Box::new(link));
+        }
+        append_to_head(root, nodes)
+    }
+}
+
+impl Stylesheet {
+    pub fn critical(&self, root : &dyn Node) -> CriticalCss {
+        critical_css(&self.to_string(), root)
+    }
+}
+
+// Splits CSS into its top level statements, as the trimmed text before each block or `;`, and the
+// block's contents. Comments are dropped, and anything unterminated runs to the end.
+fn statements(css : &str) -> Vec<(&str, Option<&str>)> {
+    let bytes = css.as_bytes();
+    let mut statements = Vec::new();
+    let mut start = 0;
+    let mut index = 0;
+
+    while index < bytes.len() {
+        match bytes[index] {
+            b'/' if bytes.get(index + 1) == Some(&b'*') => {
+                let end = comment_end(css, index).unwrap_or(css.len());
+                if css[start..index].trim().is_empty() {
+                    start = end;
+                }
+                index = end;
+            },
+            b'"' | b'\'' => index = string_end(css, index).unwrap_or(css.len()),
+            b';' => {
+                let prelude = css[start..index].trim();
+                if !prelude.is_empty() {
+                    statements.push((prelude, None));
+                }
+                index += 1;
+                start = index;
+            },
+            b'{' => {
+                let end = block_end(css, index + 1);
+                statements.push((css[start..index].trim(), Some(css[index + 1..end].trim())));
+                index = end + 1;
+                start = index;
+            },
+            _ => index += 1,
+        }
+    }
+
+    statements
+}
+
+// Index of the `}` closing a block whose contents start at `index`, or the end of the CSS.
+fn block_end(css : &str, index : usize) -> usize {
+    let bytes = css.as_bytes();
+    let mut depth = 0;
+    let mut index = index;
+    while index < bytes.len() {
+        match bytes[index] {
+            b'"' | b'\'' => {
+                index = string_end(css, index).unwrap_or(css.len());
+                continue;
+            },
+            b'/' if bytes.get(index + 1) == Some(&b'*') => {
+                index = comment_end(css, index).unwrap_or(css.len());
+                continue;
+            },
+            b'{' => depth += 1,
+            b'}' if depth == 0 => return index,
+            b'}' => depth -= 1,
+            _ => (),
+        }
+        index += 1;
+    }
+    bytes.len()
+}
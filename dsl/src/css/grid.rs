@@ -0,0 +1,139 @@
+use std::fmt;
+
+use crate::css::{CssValue, GridArea, Style, StyleItem, display, grid_area, grid_template_areas, grid_template_columns, grid_template_rows};
+
+#[derive(Debug, PartialEq)]
+pub enum GridError {
+    // Grid without any rows or columns.
+    Empty,
+    // Area name which isn't a CSS identifier.
+    InvalidName(String),
+    // Area whose cells don't form a single filled rectangle.
+    NotRectangular(String),
+}
+impl fmt::Display for GridError {
+    fn fmt(&self, f : &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GridError::Empty => write!(f, "grid has no cells"),
+            GridError::InvalidName(name) => write!(f, "`{}` is not a valid grid area name", name),
+            GridError::NotRectangular(name) => write!(f, "grid area `{}` is not a rectangle", name),
+        }
+    }
+}
+impl std::error::Error for GridError {}
+
+// Grid of named areas, with a row of names for each row of the grid. Its rows and columns are
+// sized with `rows` and `columns`, which take exactly one size per row or column.
+pub struct GridLayout<const R : usize, const C : usize> {
+    areas : Vec<Vec<String>>,
+    rows : Option<Vec<String>>,
+    columns : Option<Vec<String>>,
+}
+// Lays out named areas, such as
+// `grid_areas([["header", "header"], ["sidebar", "main"]])`, where `.` leaves a cell empty.
+pub fn grid_areas<const R : usize, const C : usize>(areas : [[&str; C]; R]) -> Result<GridLayout<R, C>, GridError> {
+    if R == 0 || C == 0 {
+        return Err(GridError::Empty);
+    }
+
+    let areas : Vec<Vec<String>> = areas.iter().map(|row| row.iter().map(|name| name.to_string()).collect()).collect();
+    let layout = GridLayout {
+        areas,
+        rows : None,
+        columns : None,
+    };
+
+    for name in layout.names() {
+        if !is_identifier(name) {
+            return Err(GridError::InvalidName(name.to_string()));
+        }
+
+        let cells : Vec<(usize, usize)> = layout.cells(name).collect();
+        let rows = cells.iter().map(|(row, _)| *row);
+        let columns = cells.iter().map(|(_, column)| *column);
+        let height = rows.clone().max().unwrap() - rows.min().unwrap() + 1;
+        let width = columns.clone().max().unwrap() - columns.min().unwrap() + 1;
+        if cells.len() != height * width {
+            return Err(GridError::NotRectangular(name.to_string()));
+        }
+    }
+
+    Ok(layout)
+}
+impl<const R : usize, const C : usize> GridLayout<R, C> {
+    pub fn rows<V : CssValue>(mut self, sizes : [V; R]) -> GridLayout<R, C> {
+        self.rows = Some(sizes.iter().map(|size| size.to_string()).collect());
+        self
+    }
+
+    pub fn columns<V : CssValue>(mut self, sizes : [V; C]) -> GridLayout<R, C> {
+        self.columns = Some(sizes.iter().map(|size| size.to_string()).collect());
+        self
+    }
+
+    // Names of the areas, in the order they first appear.
+    pub fn names(&self) -> Vec<&str> {
+        let mut names = Vec::new();
+        for name in self.areas.iter().flatten() {
+            if !is_empty_cell(name) && !names.contains(&name.as_str()) {
+                names.push(name.as_str());
+            }
+        }
+        names
+    }
+
+    // `grid-area` placing a child of the container in the named area, if the grid has it.
+    pub fn area(&self, name : &str) -> Option<GridArea> {
+        match self.names().contains(&name) {
+            true => Some(grid_area(name)),
+            false => None,
+        }
+    }
+
+    fn cells<'a>(&'a self, name : &'a str) -> impl Iterator<Item = (usize, usize)> + 'a {
+        self.areas
+        .iter()
+        .enumerate()
+        .flat_map(move |(row, names)| {
+            names
+            .iter()
+            .enumerate()
+            .filter(move |(_, cell)| cell.as_str() == name)
+            .map(move |(column, _)| (row, column))
+        })
+    }
+
+    fn template_areas(&self) -> String {
+        // Single quoted, so that the value can be written within a `style` attribute.
+        self.areas
+        .iter()
+        .map(|row| format!("'{}'", row.join(" ")))
+        .collect::<Vec<_>>()
+        .join(" ")
+    }
+}
+// Adding the layout to the style of the container makes it a grid with the layout's template, such
+// as `style![&layout, gap("1rem")]`.
+impl<const R : usize, const C : usize> StyleItem for &GridLayout<R, C> {
+    fn add_to(self, style : &mut Style) {
+        style.with_prop(display("grid"));
+        style.with_prop(grid_template_areas(self.template_areas()));
+        if let Some(rows) = &self.rows {
+            style.with_prop(grid_template_rows(rows.join(" ")));
+        }
+        if let Some(columns) = &self.columns {
+            style.with_prop(grid_template_columns(columns.join(" ")));
+        }
+    }
+}
+
+// Cells of only `.`, which belong to no area.
+fn is_empty_cell(name : &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c == '.')
+}
+
+fn is_identifier(name : &str) -> bool {
+    let name = name.strip_prefix('-').unwrap_or(name);
+    name.starts_with(|c : char| c.is_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_alphanumeric() || c == '-' || c == '_')
+}
@@ -0,0 +1,67 @@
+use std::collections::HashMap;
+
+use crate::attr;
+use crate::css::{Style, Stylesheet, Condition, rule};
+use crate::nodes::{Node, Element, for_each_element_mut};
+
+// Moves every style with variants out of the tree and into the returned stylesheet, giving each
+// element a generated class in its place. Identical styles share a class.
+pub fn hoist_variants(root : &mut dyn Node) -> Stylesheet {
+    let mut stylesheet = Stylesheet::new();
+    // Class of each style hoisted so far, by its fingerprint.
+    let mut classes : HashMap<String, String> = HashMap::new();
+
+    for_each_element_mut(root, &mut |element| {
+        match element.css_props_mut() {
+            Some(style) if !style.variants.is_empty() => (),
+            _ => return,
+        }
+
+        let style = element.css_props_mut().take().unwrap();
+        let fingerprint = fingerprint(&style);
+        let class = match classes.get(&fingerprint) {
+            Some(class) => class.clone(),
+            None => {
+                // Named after the hash of the style, so names stay the same between builds, with a
+                // suffix if another style's hash gives the same name.
+                let name = format!("s{:08x}", crate::hash(&fingerprint) as u32);
+                let mut class = name.clone();
+                let mut suffix = 1;
+                while classes.values().any(|existing| *existing == class) {
+                    class = format!("{}-{}", name, suffix);
+                    suffix += 1;
+                }
+                stylesheet.with_rule(rule(&format!(".{}", class), style));
+                classes.insert(fingerprint, class.clone());
+                class
+            },
+        };
+        add_class(element, &class);
+    });
+
+    stylesheet
+}
+
+// Everything which the rendered rules of a style depend on.
+fn fingerprint(style : &Style) -> String {
+    let mut rendered = style.to_string();
+    for variant in &style.variants {
+        let condition = match &variant.condition {
+            Condition::Selector(selector) => selector.clone(),
+            Condition::Media(query) => format!("@media {}", query),
+        };
+        rendered.push_str(&format!("{}{{{}}}", condition, fingerprint(&variant.style)));
+    }
+    rendered
+}
+
+pub(crate) fn add_class(element : &mut dyn Element, class : &str) {
+    let attributes = element.attributes_mut();
+    match attributes.iter().position(|attribute| attribute.name() == "class") {
+        Some(index) => {
+            let classes = format!("{} {}", attributes[index].value(), class);
+            attributes[index] = Box::new(attr::class(&classes));
+        },
+        None => attributes.push(Box::new(attr::class(class))),
+    }
+}
@@ -0,0 +1,134 @@
+use std::fmt;
+
+use crate::css::{Style, custom_prop, prop_from_name};
+
+#[derive(Debug, PartialEq)]
+pub enum CssParseError {
+    // Declaration without a `:` between its name and value.
+    MissingColon(String),
+    MissingName(String),
+    MissingValue(String),
+    // Unclosed string, comment or bracket.
+    Unterminated,
+}
+impl fmt::Display for CssParseError {
+    fn fmt(&self, f : &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CssParseError::MissingColon(declaration) => write!(f, "expected `:` in declaration `{}`", declaration),
+            CssParseError::MissingName(declaration) => write!(f, "missing property name in declaration `{}`", declaration),
+            CssParseError::MissingValue(name) => write!(f, "missing value for property `{}`", name),
+            CssParseError::Unterminated => write!(f, "unterminated string, comment or bracket"),
+        }
+    }
+}
+impl std::error::Error for CssParseError {}
+
+impl Style {
+    // Parses declarations as written in a `style` attribute, such as `color: red; margin: 0 auto`.
+    // Known properties become their generated structs, and anything else a custom property.
+    pub fn parse(declarations : &str) -> Result<Style, CssParseError> {
+        let mut style = Style::new();
+
+        for declaration in split_declarations(declarations)? {
+            let (name, value) = match declaration.split_once(':') {
+                Some((name, value)) => (name.trim(), value.trim()),
+                None => return Err(CssParseError::MissingColon(declaration)),
+            };
+
+            if name.is_empty() {
+                return Err(CssParseError::MissingName(declaration));
+            }
+
+            let (value, important) = strip_important(value);
+            if value.is_empty() {
+                return Err(CssParseError::MissingValue(String::from(name)));
+            }
+            let value = if important { format!("{} !important", value) } else { String::from(value) };
+
+            // Property names are ASCII case-insensitive, but custom property names aren't.
+            let name = if name.starts_with("--") { String::from(name) } else { name.to_ascii_lowercase() };
+            match prop_from_name(&name, &value) {
+                Some(prop) => style.props.push(prop),
+                None => style.with_prop(custom_prop(&name, value)),
+            }
+        }
+
+        Ok(style)
+    }
+}
+
+// Splits on the `;` between declarations, ignoring those in strings and brackets, such as in
+// `url("data:image/png;base64,...")`, and dropping comments.
+fn split_declarations(declarations : &str) -> Result<Vec<String>, CssParseError> {
+    let bytes = declarations.as_bytes();
+    let mut split = Vec::new();
+    let mut current = String::new();
+    let mut depth = 0;
+    // Start of the text not yet added to `current`.
+    let mut start = 0;
+    let mut index = 0;
+
+    while index < bytes.len() {
+        match bytes[index] {
+            b'/' if bytes.get(index + 1) == Some(&b'*') => {
+                current.push_str(&declarations[start..index]);
+                index = comment_end(declarations, index).ok_or(CssParseError::Unterminated)?;
+                start = index;
+            },
+            b'"' | b'\'' => index = string_end(declarations, index).ok_or(CssParseError::Unterminated)?,
+            b'(' | b'[' => {
+                depth += 1;
+                index += 1;
+            },
+            b')' | b']' => {
+                depth -= 1;
+                index += 1;
+            },
+            b';' if depth == 0 => {
+                current.push_str(&declarations[start..index]);
+                split.push(std::mem::take(&mut current));
+                index += 1;
+                start = index;
+            },
+            _ => index += 1,
+        }
+    }
+
+    if depth != 0 {
+        return Err(CssParseError::Unterminated);
+    }
+    current.push_str(&declarations[start..]);
+    split.push(current);
+
+    Ok(split.into_iter().map(|declaration| declaration.trim().to_string()).filter(|declaration| !declaration.is_empty()).collect())
+}
+
+// Index just after the string whose opening quote is at `index`, or `None` if it isn't closed.
+// Shared by everything which splits CSS, so that nothing splits within a string.
+pub(crate) fn string_end(css : &str, index : usize) -> Option<usize> {
+    let bytes = css.as_bytes();
+    let quote = bytes[index];
+    let mut index = index + 1;
+    while index < bytes.len() {
+        match bytes[index] {
+            b'\\' => index += 2,
+            c if c == quote => return Some(index + 1),
+            _ => index += 1,
+        }
+    }
+    None
+}
+
+// Index just after the comment whose `/*` is at `index`, or `None` if it isn't closed.
+pub(crate) fn comment_end(css : &str, index : usize) -> Option<usize> {
+    css[index + 2..].find("*/").map(|end| index + 2 + end + 2)
+}
+
+pub(crate) fn strip_important(value : &str) -> (&str, bool) {
+    if let Some(index) = value.rfind('!') {
+        if value[index + 1..].trim().eq_ignore_ascii_case("important") {
+            return (value[..index].trim_end(), true);
+        }
+    }
+    (value, false)
+}
@@ -0,0 +1,162 @@
+use std::sync::OnceLock;
+
+use crate::css::{CssProp, Style, custom_prop};
+use crate::nodes::{Node, for_each_element_mut};
+
+// Vendored table of which properties need vendor prefixes in which browsers.
+const PREFIXES : &str = include_str!("prefixes.txt");
+
+struct Prefix {
+    property : &'static str,
+    prefix : &'static str,
+    browser : Browser,
+    // Version from which the property works without the prefix, or `None` if it never does.
+    unprefixed_from : Option<f32>,
+}
+
+fn prefixes() -> &'static [Prefix] {
+    static TABLE : OnceLock<Vec<Prefix>> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        PREFIXES
+        .lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            let fields : Vec<&'static str> = line.split_whitespace().collect();
+            Prefix {
+                property : fields[0],
+                prefix : fields[1],
+                browser : Browser::from_name(fields[2]).expect("unknown browser in prefix table"),
+                unprefixed_from : fields[3].parse().ok(),
+            }
+        })
+        .collect()
+    })
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Browser {
+    Chrome,
+    Edge,
+    Firefox,
+    Safari,
+    IosSafari,
+    Samsung,
+    Opera,
+}
+impl Browser {
+    // Name of the browser as used by browserslist, such as `ios_saf`.
+    pub fn from_name(name : &str) -> Option<Browser> {
+        match name.to_ascii_lowercase().as_str() {
+            "chrome" => Some(Browser::Chrome),
+            "edge" => Some(Browser::Edge),
+            "firefox" | "ff" => Some(Browser::Firefox),
+            "safari" => Some(Browser::Safari),
+            "ios_saf" | "ios" => Some(Browser::IosSafari),
+            "samsung" => Some(Browser::Samsung),
+            "opera" => Some(Browser::Opera),
+            _ => None,
+        }
+    }
+}
+
+// Oldest version of each browser which rendered CSS needs to support. No targets means no
+// prefixes are added.
+#[derive(Clone, Default)]
+pub struct Targets {
+    browsers : Vec<(Browser, f32)>,
+}
+impl Targets {
+    pub fn new() -> Targets {
+        Targets {
+            browsers : Vec::new(),
+        }
+    }
+
+    pub fn browser(mut self, browser : Browser, version : f32) -> Targets {
+        self.browsers.push((browser, version));
+        self
+    }
+
+    // Parses a browserslist style query of minimum versions, such as
+    // `chrome >= 100, safari >= 15.4, ios_saf >= 15`.
+    pub fn parse(query : &str) -> Option<Targets> {
+        let mut targets = Targets::new();
+        for target in query.split(',').map(|target| target.trim()).filter(|target| !target.is_empty()) {
+            let (name, version) = match target.split_once(">=") {
+                Some((name, version)) => (name.trim(), version.trim()),
+                None => target.split_once(' ')?,
+            };
+            targets = targets.browser(Browser::from_name(name)?, version.trim().parse().ok()?);
+        }
+        Some(targets)
+    }
+
+    // Prefixes needed by the property for any of the targets.
+    fn prefixes_for(&self, property : &str) -> Vec<&'static str> {
+        let mut needed = Vec::new();
+        for prefix in prefixes().iter().filter(|prefix| prefix.property == property) {
+            let is_needed = self.browsers.iter().any(|(browser, version)| {
+                *browser == prefix.browser && prefix.unprefixed_from.is_none_or(|from| *version < from)
+            });
+            if is_needed && !needed.contains(&prefix.prefix) {
+                needed.push(prefix.prefix);
+            }
+        }
+        needed
+    }
+}
+
+// Copy of the declarations with prefixed declarations ahead of those which need them. Prefixes
+// already declared, such as by `autoprefix`, aren't added again.
+pub(crate) fn prefixed(props : &[Box<dyn CssProp>], targets : &Targets) -> Vec<Box<dyn CssProp>> {
+    let mut prefixed : Vec<Box<dyn CssProp>> = Vec::new();
+    for prop in props {
+        let (name, value) = (prop.name(), prop.value());
+        for prefix in targets.prefixes_for(&name) {
+            let prefixed_name = format!("{}{}", prefix, name);
+            if !props.iter().any(|prop| prop.name() == prefixed_name) {
+                prefixed.push(Box::new(custom_prop(&prefixed_name, &value)));
+            }
+        }
+        prefixed.push(Box::new(custom_prop(&name, value)));
+    }
+    prefixed
+}
+
+impl Style {
+    // Adds the vendor prefixed declarations needed by the targets, ahead of the declarations which
+    // need them, including within variants. Declarations which already have them are skipped.
+    pub fn prefix(&mut self, targets : &Targets) {
+        let mut index = 0;
+        while index < self.props.len() {
+            let (name, value) = (self.props[index].name(), self.props[index].value());
+            for prefix in targets.prefixes_for(&name) {
+                let prefixed_name = format!("{}{}", prefix, name);
+                if !self.props.iter().any(|prop| prop.name() == prefixed_name) {
+                    self.props.insert(index, Box::new(custom_prop(&prefixed_name, &value)));
+                    index += 1;
+                }
+            }
+            index += 1;
+        }
+
+        for variant in &mut self.variants {
+            variant.style.prefix(targets);
+        }
+    }
+
+    // Renders the declarations with the vendor prefixes needed by the targets.
+    pub fn render(&self, targets : &Targets) -> String {
+        crate::css::shorthand::collapsed(&prefixed(&self.props, targets))
+    }
+}
+
+// Adds the vendor prefixes needed by the targets to every inline style in the tree.
+pub fn autoprefix(root : &mut dyn Node, targets : &Targets) {
+    for_each_element_mut(root, &mut |element| {
+        if let Some(style) = element.css_props_mut() {
+            style.prefix(targets);
+        }
+    });
+}
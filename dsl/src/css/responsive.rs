@@ -0,0 +1,129 @@
+use crate::css::{Condition, CssValue, Style, Variant, custom_prop, prop_from_name};
+
+// Viewport width from which a responsive value applies, mobile first. The defaults are in
+// `breakpoints`, and one-off breakpoints can be given to `Responsive::at`, such as
+// `Breakpoint::new("50rem")`.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Breakpoint {
+    min_width : &'static str,
+}
+impl Breakpoint {
+    pub const fn new(min_width : &'static str) -> Breakpoint {
+        Breakpoint {
+            min_width,
+        }
+    }
+
+    pub fn query(&self) -> String {
+        format!("(min-width: {})", self.min_width)
+    }
+}
+
+// Default breakpoints, which the keys of `responsive!` name. Lower case to match those keys, and
+// kept in their own module so that names as short as `sm` aren't brought in by `css::*`. A project
+// declares its own breakpoints once by declaring a `breakpoints` module where it uses
+// `responsive!`, which shadows this one:
+//
+//     #[allow(non_upper_case_globals)]
+//     mod breakpoints {
+//         pub use dsl::css::breakpoints::*;
+//         pub const md : dsl::css::Breakpoint = dsl::css::Breakpoint::new("50rem");
+//         pub const tablet : dsl::css::Breakpoint = dsl::css::Breakpoint::new("40rem");
+//     }
+#[allow(non_upper_case_globals)]
+pub mod breakpoints {
+    use super::Breakpoint;
+
+    pub const sm : Breakpoint = Breakpoint::new("640px");
+    pub const md : Breakpoint = Breakpoint::new("768px");
+    pub const lg : Breakpoint = Breakpoint::new("1024px");
+    pub const xl : Breakpoint = Breakpoint::new("1280px");
+    pub const xxl : Breakpoint = Breakpoint::new("1536px");
+}
+
+// Value which changes at breakpoints, from `responsive!`. It renders as the base value, and the
+// property given it adds a media variant to its style for each breakpoint, which `rule` and
+// `hoist_variants` write out as media query rules.
+pub struct Responsive {
+    base : String,
+    breakpoints : Vec<(Breakpoint, String)>,
+}
+impl Responsive {
+    pub fn new<V>(base : V) -> Responsive
+        where V : CssValue {
+        Responsive {
+            base : base.to_string(),
+            breakpoints : Vec::new(),
+        }
+    }
+
+    pub fn at<V>(mut self, breakpoint : Breakpoint, value : V) -> Responsive
+        where V : CssValue {
+        self.breakpoints.push((breakpoint, value.to_string()));
+        self
+    }
+}
+impl ToString for Responsive {
+    fn to_string(&self) -> String {
+        self.base.clone()
+    }
+}
+impl CssValue for Responsive {
+    fn responsive(&self) -> Vec<(String, String)> {
+        self.breakpoints.iter().map(|(breakpoint, value)| (breakpoint.query(), value.clone())).collect()
+    }
+}
+
+// Width in pixels of a `(min-width: ...)` query, taking `rem` and `em` as 16px.
+pub(crate) fn min_width(query : &str) -> Option<f64> {
+    let width = query.trim().strip_prefix("(min-width:")?.strip_suffix(')')?.trim();
+    let (number, scale) = match width.strip_suffix("rem").or_else(|| width.strip_suffix("em")) {
+        Some(number) => (number, 16.0),
+        None => (width.strip_suffix("px")?, 1.0),
+    };
+    number.trim().parse::<f64>().ok().map(|number| number * scale)
+}
+
+// Adds a property's values for each breakpoint to the media variant of the style for that
+// breakpoint, so properties sharing breakpoints are written in the same media query. New media
+// variants go before any for a wider breakpoint, so that the widest matching one wins whatever
+// order the breakpoints were given in.
+pub(crate) fn add_responsive(style : &mut Style, name : &str, responsive : Vec<(String, String)>) {
+    for (query, value) in responsive {
+        let prop = prop_from_name(name, &value).unwrap_or_else(|| Box::new(custom_prop(name, value)));
+        let existing = style.variants.iter_mut().find(|variant| match &variant.condition {
+            Condition::Media(existing) => *existing == query,
+            _ => false,
+        });
+        match existing {
+            Some(variant) => variant.style.props.push(prop),
+            None => {
+                let mut variant_style = Style::new();
+                variant_style.props.push(prop);
+                let variant = Variant {
+                    condition : Condition::Media(query.clone()),
+                    style : variant_style,
+                };
+                let wider = min_width(&query).and_then(|width| {
+                    style.variants.iter().position(|variant| match &variant.condition {
+                        Condition::Media(existing) => min_width(existing).is_some_and(|existing| existing > width),
+                        _ => false,
+                    })
+                });
+                match wider {
+                    Some(index) => style.variants.insert(index, variant),
+                    None => style.variants.push(variant),
+                }
+            },
+        }
+    }
+}
+
+// Keys are looked up in whichever `breakpoints` module is in scope where the macro is used, being
+// the defaults from `css::*` unless the project declares its own.
+#[macro_export]
+macro_rules! responsive {
+    (base : $base:expr $(, $breakpoint:ident : $value:expr)* $(,)?) => {
+        $crate::css::Responsive::new($base)$(.at(breakpoints::$breakpoint, $value))*
+    }
+}
@@ -0,0 +1,311 @@
+use crate::css::{CssProp, Style, custom_prop, prop_from_name};
+use crate::css::parse::strip_important;
+
+// Shorthands setting their longhands in the order top, right, bottom, left, with the usual one to
+// four values.
+const BOX_SHORTHANDS : &[(&str, [&str; 4])] = &[
+    ("margin", ["margin-top", "margin-right", "margin-bottom", "margin-left"]),
+    ("padding", ["padding-top", "padding-right", "padding-bottom", "padding-left"]),
+    ("inset", ["top", "right", "bottom", "left"]),
+    ("border-width", ["border-top-width", "border-right-width", "border-bottom-width", "border-left-width"]),
+    ("border-style", ["border-top-style", "border-right-style", "border-bottom-style", "border-left-style"]),
+    ("border-color", ["border-top-color", "border-right-color", "border-bottom-color", "border-left-color"]),
+    ("border-radius", ["border-top-left-radius", "border-top-right-radius", "border-bottom-right-radius", "border-bottom-left-radius"]),
+    ("scroll-margin", ["scroll-margin-top", "scroll-margin-right", "scroll-margin-bottom", "scroll-margin-left"]),
+    ("scroll-padding", ["scroll-padding-top", "scroll-padding-right", "scroll-padding-bottom", "scroll-padding-left"]),
+];
+
+// Shorthands of two longhands, where one value sets both.
+const PAIR_SHORTHANDS : &[(&str, [&str; 2])] = &[
+    ("gap", ["row-gap", "column-gap"]),
+    ("overflow", ["overflow-x", "overflow-y"]),
+    ("overscroll-behavior", ["overscroll-behavior-x", "overscroll-behavior-y"]),
+    ("place-content", ["align-content", "justify-content"]),
+    ("place-items", ["align-items", "justify-items"]),
+    ("place-self", ["align-self", "justify-self"]),
+    ("margin-block", ["margin-block-start", "margin-block-end"]),
+    ("margin-inline", ["margin-inline-start", "margin-inline-end"]),
+    ("padding-block", ["padding-block-start", "padding-block-end"]),
+    ("padding-inline", ["padding-inline-start", "padding-inline-end"]),
+    ("inset-block", ["inset-block-start", "inset-block-end"]),
+    ("inset-inline", ["inset-inline-start", "inset-inline-end"]),
+];
+
+// Shorthands resetting longhands other than those above which aren't named after them.
+const OTHER_LONGHANDS : &[(&str, &[&str])] = &[
+    ("font", &["line-height"]),
+    ("columns", &["column-width", "column-count"]),
+    ("flex-flow", &["flex-direction", "flex-wrap"]),
+    ("grid-area", &["grid-row-start", "grid-column-start", "grid-row-end", "grid-column-end"]),
+];
+
+// Shorthands resetting every property named after them, such as `border` and `border-top-color`,
+// apart from those in `NOT_RESET`.
+const PREFIX_SHORTHANDS : &[&str] = &[
+    "animation", "background", "border", "border-top", "border-right", "border-bottom", "border-left",
+    "border-block", "border-inline", "border-image", "column-rule", "flex", "font", "grid",
+    "grid-column", "grid-row", "grid-template", "list-style", "mask", "outline", "text-decoration",
+    "text-emphasis", "transition",
+];
+
+const NOT_RESET : &[&str] = &[
+    "border-collapse", "border-spacing", "outline-offset", "font-feature-settings", "font-variation-settings",
+    "background-blend-mode", "text-decoration-skip-ink", "flex-direction", "flex-wrap", "flex-flow",
+];
+
+// Whether declaring `shorthand` resets `longhand`, so that an earlier declaration of `longhand`
+// no longer applies.
+pub(crate) fn resets(shorthand : &str, longhand : &str) -> bool {
+    BOX_SHORTHANDS.iter().any(|(name, longhands)| *name == shorthand && longhands.contains(&longhand))
+    || PAIR_SHORTHANDS.iter().any(|(name, longhands)| *name == shorthand && longhands.contains(&longhand))
+    || OTHER_LONGHANDS.iter().any(|(name, longhands)| *name == shorthand && longhands.contains(&longhand))
+    || (
+        PREFIX_SHORTHANDS.contains(&shorthand)
+        && longhand.strip_prefix(shorthand).is_some_and(|rest| rest.starts_with('-'))
+        && !NOT_RESET.contains(&longhand)
+        // `border` and its sides leave the corner radii alone.
+        && !(shorthand.starts_with("border") && longhand.ends_with("-radius"))
+    )
+}
+
+// Number of shorthands nested above a property, so that ordering declarations by it puts every
+// shorthand before the longhands it resets.
+pub(crate) fn shorthand_depth(name : &str) -> usize {
+    BOX_SHORTHANDS.iter().map(|(shorthand, _)| *shorthand)
+    .chain(PAIR_SHORTHANDS.iter().map(|(shorthand, _)| *shorthand))
+    .chain(OTHER_LONGHANDS.iter().map(|(shorthand, _)| *shorthand))
+    .chain(PREFIX_SHORTHANDS.iter().copied())
+    .filter(|shorthand| resets(shorthand, name))
+    .map(|shorthand| shorthand_depth(shorthand) + 1)
+    .max()
+    .unwrap_or(0)
+}
+
+impl Style {
+    // Expands the shorthands above into their longhands, and keeps only the declaration of each
+    // property which applies, so that `margin: 0` followed by `margin-top: 4px` becomes the four
+    // margin longhands. These are collapsed back into the shortest shorthand when rendered.
+    pub fn normalize(&mut self) {
+        let mut expanded : Vec<Box<dyn CssProp>> = Vec::new();
+        for prop in self.props.drain(..) {
+            match expand(prop.as_ref()) {
+                Some(longhands) => expanded.extend(longhands),
+                None => expanded.push(prop),
+            }
+        }
+
+        // A later declaration of a property overrides an earlier one, unless only the earlier one
+        // is `!important`.
+        let mut applies = vec![true; expanded.len()];
+        for later in 0..expanded.len() {
+            for earlier in 0..later {
+                if applies[earlier] && expanded[earlier].name() == expanded[later].name() {
+                    if is_important(expanded[earlier].as_ref()) && !is_important(expanded[later].as_ref()) {
+                        applies[later] = false;
+                    }
+                    else {
+                        applies[earlier] = false;
+                    }
+                }
+            }
+        }
+
+        self.props = expanded.into_iter().zip(applies).filter(|(_, applies)| *applies).map(|(prop, _)| prop).collect();
+
+        for variant in &mut self.variants {
+            variant.style.normalize();
+        }
+    }
+
+    // Adds every declaration and variant of `other`, which override those already in the style.
+    pub fn merge(&mut self, other : Style) {
+        self.props.extend(other.props);
+        self.variants.extend(other.variants);
+        self.normalize();
+    }
+}
+
+// Keywords which combine with the following or preceding word into a single alignment value.
+const ALIGNMENT_MODIFIERS : &[&str] = &["safe", "unsafe", "first", "last", "legacy"];
+
+fn is_important(prop : &dyn CssProp) -> bool {
+    strip_important(&prop.value()).1
+}
+
+fn new_prop(name : &str, value : &str) -> Box<dyn CssProp> {
+    prop_from_name(name, value).unwrap_or_else(|| Box::new(custom_prop(name, value)))
+}
+
+// Splits a value on its top level whitespace, so `calc(1px + 2px) 0` is two values.
+fn split_values(value : &str) -> Vec<&str> {
+    let mut values = Vec::new();
+    let mut depth = 0;
+    let mut start = None;
+    for (index, c) in value.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            _ => (),
+        }
+        if c.is_whitespace() && depth == 0 {
+            if let Some(start) = start.take() {
+                values.push(&value[start..index]);
+            }
+        }
+        else if start.is_none() {
+            start = Some(index);
+        }
+    }
+    if let Some(start) = start {
+        values.push(&value[start..]);
+    }
+    values
+}
+
+fn expand(prop : &dyn CssProp) -> Option<Vec<Box<dyn CssProp>>> {
+    let name = prop.name();
+    let value = prop.value();
+    let (value, important) = strip_important(&value);
+
+    // A `var()` could stand for any number of values, so can't be expanded.
+    if value.contains("var(") || value.contains('/') {
+        return None;
+    }
+
+    let values = split_values(value);
+
+    // Alignment values such as `safe center` or `first baseline` take more than one word, so
+    // `place-items: safe center` sets both longhands to `safe center`, not one to each word.
+    if values.iter().any(|value| ALIGNMENT_MODIFIERS.contains(value)) {
+        return None;
+    }
+
+    let longhands : Vec<(&str, &str)> =
+        if let Some((_, longhands)) = BOX_SHORTHANDS.iter().find(|(shorthand, _)| *shorthand == name) {
+            let [top, right, bottom, left] = match values[..] {
+                [all] => [all, all, all, all],
+                [vertical, horizontal] => [vertical, horizontal, vertical, horizontal],
+                [top, horizontal, bottom] => [top, horizontal, bottom, horizontal],
+                [top, right, bottom, left] => [top, right, bottom, left],
+                _ => return None,
+            };
+            longhands.iter().copied().zip([top, right, bottom, left]).collect()
+        }
+        else if let Some((_, longhands)) = PAIR_SHORTHANDS.iter().find(|(shorthand, _)| *shorthand == name) {
+            let [first, second] = match values[..] {
+                [both] => [both, both],
+                [first, second] => [first, second],
+                _ => return None,
+            };
+            longhands.iter().copied().zip([first, second]).collect()
+        }
+        else {
+            return None;
+        };
+
+    Some(
+        longhands
+        .into_iter()
+        .map(|(longhand, value)| {
+            if important {
+                new_prop(longhand, &format!("{} !important", value))
+            }
+            else {
+                new_prop(longhand, value)
+            }
+        })
+        .collect()
+    )
+}
+
+fn collapse_box(values : &[&str]) -> String {
+    let (top, right, bottom, left) = (values[0], values[1], values[2], values[3]);
+    if left != right {
+        format!("{} {} {} {}", top, right, bottom, left)
+    }
+    else if bottom != top {
+        format!("{} {} {}", top, right, bottom)
+    }
+    else if right != top {
+        format!("{} {}", top, right)
+    }
+    else {
+        String::from(top)
+    }
+}
+
+fn collapse_pair(values : &[&str]) -> String {
+    if values[0] == values[1] {
+        String::from(values[0])
+    }
+    else {
+        format!("{} {}", values[0], values[1])
+    }
+}
+
+// Renders the declarations, writing each complete set of longhands as its shortest shorthand.
+// A set is only collapsed when it has one of each longhand with the same importance, each a single
+// word, and no other property of the same family between them which the move could reorder.
+pub(crate) fn collapsed(props : &[Box<dyn CssProp>]) -> String {
+    let names : Vec<String> = props.iter().map(|prop| prop.name()).collect();
+    let values : Vec<String> = props.iter().map(|prop| prop.value()).collect();
+
+    // The shorthand to write in place of a declaration, or an empty string to skip it.
+    let mut replacements : Vec<Option<String>> = vec![None; props.len()];
+
+    let groups =
+        BOX_SHORTHANDS.iter().map(|(shorthand, longhands)| (*shorthand, &longhands[..], collapse_box as fn(&[&str]) -> String))
+        .chain(PAIR_SHORTHANDS.iter().map(|(shorthand, longhands)| (*shorthand, &longhands[..], collapse_pair as fn(&[&str]) -> String)));
+
+    for (shorthand, longhands, collapse) in groups {
+        let mut indices = Vec::new();
+        for longhand in longhands {
+            let matching : Vec<usize> = (0..names.len()).filter(|index| names[*index] == *longhand).collect();
+            if matching.len() != 1 || replacements[matching[0]].is_some() {
+                break;
+            }
+            indices.push(matching[0]);
+        }
+        if indices.len() != longhands.len() {
+            continue;
+        }
+
+        let parts : Vec<(&str, bool)> = indices.iter().map(|index| strip_important(&values[*index])).collect();
+        if parts.iter().any(|(_, important)| *important != parts[0].1) {
+            continue;
+        }
+
+        // The shorthand can only write one word for each longhand, so an elliptical corner such
+        // as `border-top-left-radius: 4px 8px`, or `align-items: safe center`, stays as it is.
+        if parts.iter().any(|(value, _)| split_values(value).len() != 1) {
+            continue;
+        }
+
+        let first = *indices.iter().min().unwrap();
+        let last = *indices.iter().max().unwrap();
+        let family = shorthand.split('-').next().unwrap();
+        let interleaved = (first..=last).any(|index| {
+            !indices.contains(&index) && (names[index] == shorthand || names[index].starts_with(family) || longhands.iter().any(|longhand| names[index].starts_with(longhand)))
+        });
+        if interleaved {
+            continue;
+        }
+
+        let value_parts : Vec<&str> = parts.iter().map(|(value, _)| *value).collect();
+        let mut value = collapse(&value_parts);
+        if parts[0].1 {
+            value.push_str(" !important");
+        }
+
+        for index in &indices {
+            replacements[*index] = Some(String::new());
+        }
+        replacements[first] = Some(format!("{}: {};", shorthand, value));
+    }
+
+    props
+    .iter()
+    .zip(replacements)
+    .map(|(prop, replacement)| replacement.unwrap_or_else(|| prop.to_string()))
+    .collect()
+}
@@ -0,0 +1,243 @@
+use crate::css::{Style, Condition, Targets};
+use crate::css::parse::string_end;
+use crate::nodes::{Node, ParentNode, StyleElement, Text, for_each_element_mut};
+
+// Anything which can appear at the top level of a `Stylesheet`.
+pub trait CssRule : ToString {
+    // Renders the rule with the settings of the stylesheet it is in.
+    fn render(&self, _stylesheet : &Stylesheet) -> String {
+        self.to_string()
+    }
+}
+
+// How nested rules are written out.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Nesting {
+    // Each nested rule becomes a rule of its own with the full selector, for older browsers.
+    Flatten,
+    // Nested rules are kept within their parent using CSS nesting.
+    Native,
+}
+
+// Style rule, applying a style to every element matched by its selector. Any variants of the style
+// become rules of their own.
+pub struct Rule {
+    selector : String,
+    style : Style,
+    nested : Vec<Rule>,
+}
+pub fn rule(selector : &str, style : Style) -> Rule {
+    Rule {
+        selector : String::from(selector),
+        style,
+        nested : Vec::new(),
+    }
+}
+impl Rule {
+    // Nests a rule within this one, where `&` in the selector refers to the parent selector, and a
+    // selector without `&` matches descendants.
+    pub fn nest(self, selector : &str, style : Style) -> Rule {
+        self.nest_rule(rule(selector, style))
+    }
+
+    // Nests a rule which may have nested rules of its own.
+    pub fn nest_rule(mut self, rule : Rule) -> Rule {
+        self.nested.push(rule);
+        self
+    }
+
+    fn flattened(&self, selector : &str, targets : &Targets) -> String {
+        let mut css = style_rules(selector, &self.style, targets);
+        for nested in &self.nested {
+            css.push_str(&nested.flattened(&resolve_nested(selector, &nested.selector), targets));
+        }
+        css
+    }
+
+    fn native(&self, targets : &Targets) -> String {
+        let mut block = native_block(&self.style, targets);
+        for nested in &self.nested {
+            block.push_str(&nested.native(targets));
+        }
+        format!("{} {{{}}}", self.selector, block)
+    }
+}
+impl ToString for Rule {
+    fn to_string(&self) -> String {
+        self.flattened(&self.selector, &Targets::new())
+    }
+}
+impl CssRule for Rule {
+    fn render(&self, stylesheet : &Stylesheet) -> String {
+        match stylesheet.nesting {
+            Nesting::Flatten => self.flattened(&self.selector, &stylesheet.targets),
+            Nesting::Native => self.native(&stylesheet.targets),
+        }
+    }
+}
+
+fn style_rules(selector : &str, style : &Style, targets : &Targets) -> String {
+    let mut css = String::new();
+    if !style.props.is_empty() {
+        css.push_str(&format!("{} {{{}}}", selector, style.render(targets)));
+    }
+
+    for variant in &style.variants {
+        match &variant.condition {
+            Condition::Selector(suffix) => {
+                css.push_str(&style_rules(&append_to_selectors(selector, suffix), &variant.style, targets));
+            },
+            Condition::Media(query) => {
+                css.push_str(&format!("@media {} {{{}}}", query, style_rules(selector, &variant.style, targets)));
+            },
+        }
+    }
+
+    css
+}
+
+// Contents of a rule using CSS nesting, where variants become nested rules.
+fn native_block(style : &Style, targets : &Targets) -> String {
+    let mut block = style.render(targets);
+    for variant in &style.variants {
+        match &variant.condition {
+            Condition::Selector(suffix) => {
+                block.push_str(&format!("&{} {{{}}}", suffix, native_block(&variant.style, targets)));
+            },
+            Condition::Media(query) => {
+                block.push_str(&format!("@media {} {{{}}}", query, native_block(&variant.style, targets)));
+            },
+        }
+    }
+    block
+}
+
+// Splits a selector list on its top level commas.
+pub(crate) fn split_selectors(selector : &str) -> Vec<&str> {
+    let bytes = selector.as_bytes();
+    let mut selectors = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+    let mut index = 0;
+    while index < bytes.len() {
+        match bytes[index] {
+            b'"' | b'\'' => {
+                index = string_end(selector, index).unwrap_or(bytes.len());
+                continue;
+            },
+            b'(' | b'[' => depth += 1,
+            b')' | b']' => depth -= 1,
+            b',' if depth == 0 => {
+                selectors.push(selector[start..index].trim());
+                start = index + 1;
+            },
+            _ => (),
+        }
+        index += 1;
+    }
+    selectors.push(selector[start..].trim());
+    selectors
+}
+
+// Appends to each selector of a list, so `a, b` with `:hover` becomes `a:hover, b:hover`.
+fn append_to_selectors(selector : &str, suffix : &str) -> String {
+    split_selectors(selector)
+    .iter()
+    .map(|selector| format!("{}{}", selector, suffix))
+    .collect::<Vec<_>>()
+    .join(", ")
+}
+
+// Full selector of a nested rule, so `.card` with `&:hover, h2` becomes `.card:hover, .card h2`.
+fn resolve_nested(parent : &str, nested : &str) -> String {
+    let mut selectors = Vec::new();
+    for parent in split_selectors(parent) {
+        for nested in split_selectors(nested) {
+            if nested.contains('&') {
+                selectors.push(nested.replace('&', parent));
+            }
+            else {
+                selectors.push(format!("{} {}", parent, nested));
+            }
+        }
+    }
+    selectors.join(", ")
+}
+
+pub struct Stylesheet {
+    rules : Vec<Box<dyn CssRule>>,
+    nesting : Nesting,
+    targets : Targets,
+}
+impl Stylesheet {
+    pub fn new() -> Stylesheet {
+        Stylesheet {
+            rules : Vec::new(),
+            nesting : Nesting::Flatten,
+            targets : Targets::new(),
+        }
+    }
+
+    pub fn set_nesting(&mut self, nesting : Nesting) {
+        self.nesting = nesting;
+    }
+
+    // Browsers the stylesheet is rendered for, which decides the vendor prefixes added.
+    pub fn set_targets(&mut self, targets : Targets) {
+        self.targets = targets;
+    }
+
+    pub fn targets(&self) -> &Targets {
+        &self.targets
+    }
+
+    pub fn with_rule<R : 'static>(&mut self, rule : R)
+        where R : CssRule {
+        self.rules.push(Box::new(rule));
+    }
+
+    // Renders the stylesheet as a `<style>` element, for use within `<head>`.
+    pub fn to_node(&self) -> StyleElement {
+        let mut node = StyleElement::new();
+        node.child(Text::new(&self.to_string()));
+        node
+    }
+
+    // Appends the stylesheet as a `<style>` element to the first `<head>` of the tree, returning
+    // whether there was one.
+    pub fn insert_into_head(&self, root : &mut dyn Node) -> bool {
+        append_to_head(root, vec![Box::new(self.to_node())])
+    }
+}
+
+// Appends the nodes to the first `<head>` of the tree, returning whether there was one.
+pub(crate) fn append_to_head(root : &mut dyn Node, nodes : Vec<Box<dyn Node>>) -> bool {
+    let mut nodes = Some(nodes);
+    for_each_element_mut(root, &mut |element| {
+        if element.tag_name() != "head" {
+            return;
+        }
+        if let Some(children) = element.children_mut() {
+            if let Some(nodes) = nodes.take() {
+                children.extend(nodes);
+            }
+        }
+    });
+    nodes.is_none()
+}
+impl ToString for Stylesheet {
+    fn to_string(&self) -> String {
+        self.rules.iter().map(|rule| rule.render(self)).collect()
+    }
+}
+
+#[macro_export]
+macro_rules! stylesheet {
+    ($($a:expr),*) => {
+        {
+            let mut stylesheet = Stylesheet::new();
+            $(stylesheet.with_rule($a);)*
+            stylesheet
+        }
+    }
+}
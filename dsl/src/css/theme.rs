@@ -0,0 +1,154 @@
+use crate::css::{CssRule, CssValue, Rule, Style, Stylesheet, VarRef, custom_prop, dark, rule};
+
+// Design token, being a custom property with a value for light and optionally dark color schemes.
+// Tokens are used as values through `var(--name)`, so `color(theme.primary)` follows the theme.
+#[derive(Clone, Copy)]
+pub struct Token {
+    name : &'static str,
+    light : &'static str,
+    dark : Option<&'static str>,
+}
+// Token named `--name`, with the name given without the leading dashes.
+pub fn token(name : &'static str, value : &'static str) -> Token {
+    Token {
+        name,
+        light : value,
+        dark : None,
+    }
+}
+impl Token {
+    // Value used instead when the user prefers a dark color scheme.
+    pub fn dark(mut self, value : &'static str) -> Token {
+        self.dark = Some(value);
+        self
+    }
+
+    pub fn name(&self) -> String {
+        format!("--{}", self.name)
+    }
+}
+impl ToString for Token {
+    fn to_string(&self) -> String {
+        VarRef::new(&self.name()).to_string()
+    }
+}
+impl CssValue for Token {}
+
+// Palette, spacing, typography and radius scales of a site. Any token can be replaced using
+// struct update syntax, such as `Theme { primary : token("color-primary", "teal"), ..Theme::new() }`,
+// and the theme is added to a stylesheet to define its tokens on `:root`.
+#[derive(Clone, Copy)]
+pub struct Theme {
+    pub primary : Token,
+    pub secondary : Token,
+    pub accent : Token,
+    pub background : Token,
+    pub surface : Token,
+    pub foreground : Token,
+    pub muted : Token,
+    pub border : Token,
+    pub success : Token,
+    pub warning : Token,
+    pub danger : Token,
+
+    pub space_xs : Token,
+    pub space_sm : Token,
+    pub space_md : Token,
+    pub space_lg : Token,
+    pub space_xl : Token,
+
+    pub font_sans : Token,
+    pub font_serif : Token,
+    pub font_mono : Token,
+    pub text_xs : Token,
+    pub text_sm : Token,
+    pub text_base : Token,
+    pub text_lg : Token,
+    pub text_xl : Token,
+    pub text_2xl : Token,
+    pub leading_tight : Token,
+    pub leading_normal : Token,
+
+    pub radius_sm : Token,
+    pub radius_md : Token,
+    pub radius_lg : Token,
+    pub radius_full : Token,
+}
+impl Theme {
+    pub fn new() -> Theme {
+        Theme {
+            primary : token("color-primary", "#2563eb").dark("#60a5fa"),
+            secondary : token("color-secondary", "#475569").dark("#94a3b8"),
+            accent : token("color-accent", "#7c3aed").dark("#a78bfa"),
+            background : token("color-background", "#ffffff").dark("#0f172a"),
+            surface : token("color-surface", "#f8fafc").dark("#1e293b"),
+            foreground : token("color-foreground", "#0f172a").dark("#f1f5f9"),
+            muted : token("color-muted", "#64748b").dark("#94a3b8"),
+            border : token("color-border", "#e2e8f0").dark("#334155"),
+            success : token("color-success", "#16a34a").dark("#4ade80"),
+            warning : token("color-warning", "#d97706").dark("#fbbf24"),
+            danger : token("color-danger", "#dc2626").dark("#f87171"),
+
+            space_xs : token("space-xs", "0.25rem"),
+            space_sm : token("space-sm", "0.5rem"),
+            space_md : token("space-md", "1rem"),
+            space_lg : token("space-lg", "1.5rem"),
+            space_xl : token("space-xl", "2rem"),
+
+            font_sans : token("font-sans", "system-ui, sans-serif"),
+            font_serif : token("font-serif", "Georgia, serif"),
+            font_mono : token("font-mono", "ui-monospace, monospace"),
+            text_xs : token("text-xs", "0.75rem"),
+            text_sm : token("text-sm", "0.875rem"),
+            text_base : token("text-base", "1rem"),
+            text_lg : token("text-lg", "1.125rem"),
+            text_xl : token("text-xl", "1.25rem"),
+            text_2xl : token("text-2xl", "1.5rem"),
+            leading_tight : token("leading-tight", "1.25"),
+            leading_normal : token("leading-normal", "1.5"),
+
+            radius_sm : token("radius-sm", "0.125rem"),
+            radius_md : token("radius-md", "0.375rem"),
+            radius_lg : token("radius-lg", "0.5rem"),
+            radius_full : token("radius-full", "9999px"),
+        }
+    }
+
+    pub fn tokens(&self) -> Vec<Token> {
+        vec![
+            self.primary, self.secondary, self.accent, self.background, self.surface, self.foreground,
+            self.muted, self.border, self.success, self.warning, self.danger,
+            self.space_xs, self.space_sm, self.space_md, self.space_lg, self.space_xl,
+            self.font_sans, self.font_serif, self.font_mono,
+            self.text_xs, self.text_sm, self.text_base, self.text_lg, self.text_xl, self.text_2xl,
+            self.leading_tight, self.leading_normal,
+            self.radius_sm, self.radius_md, self.radius_lg, self.radius_full,
+        ]
+    }
+
+    // `:root` rule defining every token, with the dark values in a `prefers-color-scheme` variant.
+    fn root_rule(&self) -> Rule {
+        let mut light = Style::new();
+        let mut dark_style = Style::new();
+        for token in self.tokens() {
+            light.with_prop(custom_prop(&token.name(), token.light));
+            if let Some(value) = token.dark {
+                dark_style.with_prop(custom_prop(&token.name(), value));
+            }
+        }
+        if !dark_style.props.is_empty() {
+            light.with_prop(dark(dark_style));
+        }
+        rule(":root", light)
+    }
+}
+impl ToString for Theme {
+    fn to_string(&self) -> String {
+        self.root_rule().to_string()
+    }
+}
+impl CssRule for Theme {
+    fn render(&self, stylesheet : &Stylesheet) -> String {
+        self.root_rule().render(stylesheet)
+    }
+}
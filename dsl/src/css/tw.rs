@@ -0,0 +1,195 @@
+// Utility classes, each setting a single thing, such as `class(&tw::p(4))` or
+// `classes![tw::flex(), tw::items_center(), tw::gap(2)]`. `tw::stylesheet` then writes rules for
+// only the utilities used in a tree.
+
+use std::collections::HashSet;
+
+use crate::attr::{ClassItem, ClassList};
+use crate::css::{self, Style, Stylesheet, rule};
+use crate::nodes::Node;
+
+// Class name of a utility, used wherever a `&str` is.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Utility(String);
+impl std::ops::Deref for Utility {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+impl ClassItem for Utility {
+    fn add_to(self, list : &mut ClassList) {
+        self.0.as_str().add_to(list);
+    }
+}
+
+// Utilities with a fixed class, in the order their rules are written.
+macro_rules! fixed_utilities {
+    ($($f:ident => $class:literal [$($prop:expr),*]),* $(,)?) => {
+        $(
+            pub fn $f() -> Utility {
+                Utility(String::from($class))
+            }
+        )*
+
+        const FIXED : &[&str] = &[$($class),*];
+
+        fn fixed_style(class : &str) -> Option<Style> {
+            match class {
+                $($class => Some(crate::style![$($prop),*]),)*
+                _ => None,
+            }
+        }
+    }
+}
+
+fixed_utilities! {
+    block => "block" [css::display("block")],
+    inline_block => "inline-block" [css::display("inline-block")],
+    inline => "inline" [css::display("inline")],
+    flex => "flex" [css::display("flex")],
+    inline_flex => "inline-flex" [css::display("inline-flex")],
+    grid => "grid" [css::display("grid")],
+    hidden => "hidden" [css::display("none")],
+
+    flex_row => "flex-row" [css::flex_direction("row")],
+    flex_col => "flex-col" [css::flex_direction("column")],
+    flex_wrap => "flex-wrap" [css::flex_wrap("wrap")],
+    flex_1 => "flex-1" [css::flex("1 1 0%")],
+    items_start => "items-start" [css::align_items("flex-start")],
+    items_center => "items-center" [css::align_items("center")],
+    items_end => "items-end" [css::align_items("flex-end")],
+    justify_start => "justify-start" [css::justify_content("flex-start")],
+    justify_center => "justify-center" [css::justify_content("center")],
+    justify_end => "justify-end" [css::justify_content("flex-end")],
+    justify_between => "justify-between" [css::justify_content("space-between")],
+
+    w_full => "w-full" [css::width("100%")],
+    h_full => "h-full" [css::height("100%")],
+
+    text_xs => "text-xs" [css::font_size("0.75rem"), css::line_height("1rem")],
+    text_sm => "text-sm" [css::font_size("0.875rem"), css::line_height("1.25rem")],
+    text_base => "text-base" [css::font_size("1rem"), css::line_height("1.5rem")],
+    text_lg => "text-lg" [css::font_size("1.125rem"), css::line_height("1.75rem")],
+    text_xl => "text-xl" [css::font_size("1.25rem"), css::line_height("1.75rem")],
+    text_2xl => "text-2xl" [css::font_size("1.5rem"), css::line_height("2rem")],
+    text_left => "text-left" [css::text_align("left")],
+    text_center => "text-center" [css::text_align("center")],
+    text_right => "text-right" [css::text_align("right")],
+    font_normal => "font-normal" [css::font_weight("400")],
+    font_medium => "font-medium" [css::font_weight("500")],
+    font_semibold => "font-semibold" [css::font_weight("600")],
+    font_bold => "font-bold" [css::font_weight("700")],
+
+    border => "border" [css::border_width("1px"), css::border_style("solid")],
+    rounded => "rounded" [css::border_radius("0.25rem")],
+    rounded_lg => "rounded-lg" [css::border_radius("0.5rem")],
+    rounded_full => "rounded-full" [css::border_radius("9999px")],
+}
+
+// Utilities taking a step of the spacing scale, where each step is `0.25rem`, such as `p-4` for
+// `padding: 1rem`.
+macro_rules! spacing_utilities {
+    ($($f:ident => $prefix:literal [$($prop:path),*]),* $(,)?) => {
+        $(
+            pub fn $f(step : u32) -> Utility {
+                Utility(format!("{}-{}", $prefix, step))
+            }
+        )*
+
+        const SPACING : &[&str] = &[$($prefix),*];
+
+        fn spacing_style(prefix : &str, length : &str) -> Option<Style> {
+            match prefix {
+                $($prefix => Some(crate::style![$($prop(length)),*]),)*
+                _ => None,
+            }
+        }
+    }
+}
+
+spacing_utilities! {
+    p => "p" [css::padding],
+    px => "px" [css::padding_left, css::padding_right],
+    py => "py" [css::padding_top, css::padding_bottom],
+    pt => "pt" [css::padding_top],
+    pr => "pr" [css::padding_right],
+    pb => "pb" [css::padding_bottom],
+    pl => "pl" [css::padding_left],
+    m => "m" [css::margin],
+    mx => "mx" [css::margin_left, css::margin_right],
+    my => "my" [css::margin_top, css::margin_bottom],
+    mt => "mt" [css::margin_top],
+    mr => "mr" [css::margin_right],
+    mb => "mb" [css::margin_bottom],
+    ml => "ml" [css::margin_left],
+    gap => "gap" [css::gap],
+    w => "w" [css::width],
+    h => "h" [css::height],
+}
+
+// Centers a block horizontally. It is ordered with the spacing utilities, so that it overrides
+// `m-4` but not `ml-4`.
+pub fn mx_auto() -> Utility {
+    Utility(String::from("mx-auto"))
+}
+
+// Spacing utilities which also take `auto` in place of a step.
+const AUTO_SPACING : &[&str] = &["m", "mx", "my", "mt", "mr", "mb", "ml"];
+
+// Position of a utility's rule in the stylesheet with its style, or `None` for other classes.
+fn utility(class : &str) -> Option<((usize, u32), Style)> {
+    if let Some(index) = FIXED.iter().position(|fixed| *fixed == class) {
+        return Some(((index, 0), fixed_style(class)?));
+    }
+
+    let (prefix, step) = class.rsplit_once('-')?;
+    let index = SPACING.iter().position(|spacing| *spacing == prefix)?;
+    let (step, length) = match step {
+        "auto" if AUTO_SPACING.contains(&prefix) => (u32::MAX, String::from("auto")),
+        _ => {
+            let step : u32 = step.parse().ok()?;
+            let length = match step {
+                0 => String::from("0"),
+                _ => css::rem(step as f64 * 0.25).to_string(),
+            };
+            (step, length)
+        },
+    };
+    Some(((FIXED.len() + index, step), spacing_style(prefix, &length)?))
+}
+
+fn used_classes(node : &dyn Node, used : &mut HashSet<String>) {
+    let element = match node.as_element() {
+        Some(element) => element,
+        None => return,
+    };
+    for attribute in element.attributes() {
+        if attribute.name() == "class" {
+            used.extend(attribute.value().split_whitespace().map(String::from));
+        }
+    }
+    for child in element.children().unwrap_or(&[]) {
+        used_classes(child.as_ref(), used);
+    }
+}
+
+// Stylesheet with a rule for each utility class used in the tree, and nothing for unused ones.
+pub fn stylesheet(root : &dyn Node) -> Stylesheet {
+    let mut used = HashSet::new();
+    used_classes(root, &mut used);
+
+    let mut utilities : Vec<((usize, u32), String, Style)> =
+        used
+        .into_iter()
+        .filter_map(|class| utility(&class).map(|(order, style)| (order, class, style)))
+        .collect();
+    utilities.sort_by_key(|(order, _, _)| *order);
+
+    let mut stylesheet = Stylesheet::new();
+    for (_, class, style) in utilities {
+        stylesheet.with_rule(rule(&format!(".{}", class), style));
+    }
+    stylesheet
+}
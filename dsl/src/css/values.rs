@@ -0,0 +1,266 @@
+use crate::css::{Filter, Transform};
+
+pub mod math;
+pub use math::*;
+
+// Values which can be passed to any of the generated property functions.
+pub trait CssValue : ToString {
+    // Values for wider viewports, as pairs of media query and value, which only `Responsive` has.
+    fn responsive(&self) -> Vec<(String, String)> {
+        Vec::new()
+    }
+}
+
+impl CssValue for &str {}
+impl CssValue for &String {}
+impl CssValue for String {}
+
+// Reference to a custom property, as `var(--name)` or `var(--name, fallback)`.
+pub struct VarRef {
+    name : String,
+    fallback : Option<String>,
+}
+impl VarRef {
+    pub fn new(name : &str) -> VarRef {
+        VarRef {
+            name : String::from(name),
+            fallback : None,
+        }
+    }
+}
+pub fn var<V>(name : &str, fallback : V) -> VarRef
+    where V : CssValue {
+    VarRef {
+        name : String::from(name),
+        fallback : Some(fallback.to_string()),
+    }
+}
+impl ToString for VarRef {
+    fn to_string(&self) -> String {
+        match &self.fallback {
+            Some(fallback) => format!("var({}, {})", self.name, fallback),
+            None => format!("var({})", self.name),
+        }
+    }
+}
+impl CssValue for VarRef {}
+
+// CSS-wide keywords, which every property accepts.
+pub enum Keyword {
+    Inherit,
+    Initial,
+    Unset,
+    Revert,
+    RevertLayer,
+}
+impl ToString for Keyword {
+    fn to_string(&self) -> String {
+        String::from(match self {
+            Keyword::Inherit => "inherit",
+            Keyword::Initial => "initial",
+            Keyword::Unset => "unset",
+            Keyword::Revert => "revert",
+            Keyword::RevertLayer => "revert-layer",
+        })
+    }
+}
+impl CssValue for Keyword {}
+
+// Defines CSS functions both as constructors on the struct of the property they are for, such as
+// `Transform::rotate`, and as methods on the list type they return, so that calls can be chained.
+macro_rules! css_functions {
+    ($property:ident, $list:ident, $($f:ident($($arg:ident),*) => $name:literal),* $(,)?) => {
+        impl $property {
+            $(
+                pub fn $f($($arg : impl CssValue),*) -> $list {
+                    $list(Vec::new()).$f($($arg),*)
+                }
+            )*
+        }
+
+        pub struct $list(Vec<String>);
+        impl $list {
+            $(
+                pub fn $f(mut self, $($arg : impl CssValue),*) -> $list {
+                    let args : Vec<String> = vec![$($arg.to_string()),*];
+                    self.0.push(format!("{}({})", $name, args.join(", ")));
+                    self
+                }
+            )*
+        }
+        impl ToString for $list {
+            fn to_string(&self) -> String {
+                self.0.join(" ")
+            }
+        }
+        impl CssValue for $list {}
+    }
+}
+
+// Value of `transform`, such as `Transform::translate("10px", "0").rotate("45deg")`.
+css_functions!(Transform, Transforms,
+    translate(x, y) => "translate",
+    translate_x(x) => "translateX",
+    translate_y(y) => "translateY",
+    translate_z(z) => "translateZ",
+    translate_3d(x, y, z) => "translate3d",
+    scale(x, y) => "scale",
+    scale_x(x) => "scaleX",
+    scale_y(y) => "scaleY",
+    rotate(angle) => "rotate",
+    rotate_x(angle) => "rotateX",
+    rotate_y(angle) => "rotateY",
+    rotate_z(angle) => "rotateZ",
+    skew(x, y) => "skew",
+    skew_x(angle) => "skewX",
+    skew_y(angle) => "skewY",
+    perspective(length) => "perspective",
+);
+
+// Value of `filter` and `backdrop_filter`, such as `Filter::blur("4px").brightness("120%")`.
+css_functions!(Filter, Filters,
+    blur(radius) => "blur",
+    brightness(amount) => "brightness",
+    contrast(amount) => "contrast",
+    drop_shadow(shadow) => "drop-shadow",
+    grayscale(amount) => "grayscale",
+    hue_rotate(angle) => "hue-rotate",
+    invert(amount) => "invert",
+    opacity(amount) => "opacity",
+    saturate(amount) => "saturate",
+    sepia(amount) => "sepia",
+);
+
+// Value of `background_image`, made with `linear_gradient`, `radial_gradient` or `conic_gradient`.
+pub struct Gradient {
+    kind : &'static str,
+    shape : Option<String>,
+    stops : Vec<String>,
+    repeating : bool,
+}
+// An empty shape leaves it out, for the gradient's default.
+fn gradient(kind : &'static str, shape : String) -> Gradient {
+    Gradient {
+        kind,
+        shape : if shape.trim().is_empty() { None } else { Some(shape) },
+        stops : Vec::new(),
+        repeating : false,
+    }
+}
+// Gradient along a direction, being an angle such as `45deg` or a side such as `to right`, or `""`
+// for the default of top to bottom.
+pub fn linear_gradient<V>(direction : V) -> Gradient
+    where V : CssValue {
+    gradient("linear-gradient", direction.to_string())
+}
+// Gradient out from a point, with a shape such as `circle at center`, or `""` for the default.
+pub fn radial_gradient<V>(shape : V) -> Gradient
+    where V : CssValue {
+    gradient("radial-gradient", shape.to_string())
+}
+// Gradient around a point, starting from such as `from 90deg at center`, or `""` for the default.
+pub fn conic_gradient<V>(from : V) -> Gradient
+    where V : CssValue {
+    gradient("conic-gradient", from.to_string())
+}
+impl Gradient {
+    // Adds a color stop at a position along the gradient, such as `50%`.
+    pub fn stop<C, P>(mut self, color : C, position : P) -> Gradient
+        where C : CssValue, P : CssValue {
+        self.stops.push(format!("{} {}", color.to_string(), position.to_string()));
+        self
+    }
+
+    // Adds a color stop spaced evenly between its neighbours.
+    pub fn color<C>(mut self, color : C) -> Gradient
+        where C : CssValue {
+        self.stops.push(color.to_string());
+        self
+    }
+
+    // Repeats the stops to fill the element.
+    pub fn repeating(mut self) -> Gradient {
+        self.repeating = true;
+        self
+    }
+}
+impl ToString for Gradient {
+    fn to_string(&self) -> String {
+        let args : Vec<&str> = self.shape.iter().chain(self.stops.iter()).map(|arg| arg.as_str()).collect();
+        format!("{}{}({})", if self.repeating { "repeating-" } else { "" }, self.kind, args.join(", "))
+    }
+}
+impl CssValue for Gradient {}
+
+// Value of `box_shadow` and `text_shadow`, such as `Shadow::new("0", "2px", "4px", "black")`.
+// Text shadows can't be inset or spread.
+pub struct Shadow {
+    layers : Vec<ShadowLayer>,
+}
+struct ShadowLayer {
+    x : String,
+    y : String,
+    blur : String,
+    spread : Option<String>,
+    color : String,
+    inset : bool,
+}
+impl Shadow {
+    pub fn new<X, Y, B, C>(x : X, y : Y, blur : B, color : C) -> Shadow
+        where X : CssValue, Y : CssValue, B : CssValue, C : CssValue {
+        Shadow {
+            layers : vec![ShadowLayer {
+                x : x.to_string(),
+                y : y.to_string(),
+                blur : blur.to_string(),
+                spread : None,
+                color : color.to_string(),
+                inset : false,
+            }],
+        }
+    }
+
+    // Draws the last shadow inside the element's border rather than outside it.
+    pub fn inset(mut self) -> Shadow {
+        if let Some(layer) = self.layers.last_mut() {
+            layer.inset = true;
+        }
+        self
+    }
+
+    // Grows the last shadow by a length before it is blurred.
+    pub fn spread<V>(mut self, spread : V) -> Shadow
+        where V : CssValue {
+        if let Some(layer) = self.layers.last_mut() {
+            layer.spread = Some(spread.to_string());
+        }
+        self
+    }
+
+    // Adds another shadow, drawn below the previous ones.
+    pub fn and(mut self, shadow : Shadow) -> Shadow {
+        self.layers.extend(shadow.layers);
+        self
+    }
+}
+impl ToString for Shadow {
+    fn to_string(&self) -> String {
+        self.layers
+        .iter()
+        .map(|layer| {
+            let mut parts = Vec::new();
+            if layer.inset {
+                parts.push("inset");
+            }
+            parts.extend([layer.x.as_str(), layer.y.as_str(), layer.blur.as_str()]);
+            if let Some(spread) = &layer.spread {
+                parts.push(spread);
+            }
+            parts.push(&layer.color);
+            parts.join(" ")
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+    }
+}
+impl CssValue for Shadow {}
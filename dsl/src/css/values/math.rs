@@ -0,0 +1,266 @@
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+use crate::css::CssValue;
+
+// Length in an absolute, font-relative or viewport-relative unit, such as `px(4)` or `rem(1.5)`.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Length {
+    value : f64,
+    unit : &'static str,
+}
+fn length<V : Into<f64>>(value : V, unit : &'static str) -> Length {
+    Length {
+        value : value.into(),
+        unit,
+    }
+}
+pub fn px<V : Into<f64>>(value : V) -> Length {
+    length(value, "px")
+}
+pub fn rem<V : Into<f64>>(value : V) -> Length {
+    length(value, "rem")
+}
+pub fn em<V : Into<f64>>(value : V) -> Length {
+    length(value, "em")
+}
+pub fn ch<V : Into<f64>>(value : V) -> Length {
+    length(value, "ch")
+}
+pub fn vw<V : Into<f64>>(value : V) -> Length {
+    length(value, "vw")
+}
+pub fn vh<V : Into<f64>>(value : V) -> Length {
+    length(value, "vh")
+}
+pub fn vmin<V : Into<f64>>(value : V) -> Length {
+    length(value, "vmin")
+}
+pub fn vmax<V : Into<f64>>(value : V) -> Length {
+    length(value, "vmax")
+}
+pub fn dvh<V : Into<f64>>(value : V) -> Length {
+    length(value, "dvh")
+}
+impl ToString for Length {
+    fn to_string(&self) -> String {
+        dimension(self.value, self.unit)
+    }
+}
+impl CssValue for Length {}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Percentage(pub f64);
+pub fn pct<V : Into<f64>>(value : V) -> Percentage {
+    Percentage(value.into())
+}
+impl ToString for Percentage {
+    fn to_string(&self) -> String {
+        dimension(self.0, "%")
+    }
+}
+impl CssValue for Percentage {}
+
+// Sum of lengths and percentages, where terms in the same unit are added together as the
+// expression is built, so `calc()` is only written when terms in different units remain.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Calc {
+    terms : Vec<Term>,
+}
+#[derive(Clone, PartialEq, Debug)]
+struct Term {
+    factor : f64,
+    // Unit such as `px`, or a whole function such as `min(1rem, 2vw)`.
+    atom : String,
+    is_function : bool,
+}
+impl Calc {
+    // Smallest of the values, as `min()`.
+    pub fn min<V : Into<Calc>>(values : Vec<V>) -> Calc {
+        function("min", values.into_iter().map(|value| value.into()).collect())
+    }
+
+    // Largest of the values, as `max()`.
+    pub fn max<V : Into<Calc>>(values : Vec<V>) -> Calc {
+        function("max", values.into_iter().map(|value| value.into()).collect())
+    }
+
+    // Preferred value limited to a range, as `clamp()`.
+    pub fn clamp<A, B, C>(min : A, preferred : B, max : C) -> Calc
+        where A : Into<Calc>, B : Into<Calc>, C : Into<Calc> {
+        function("clamp", vec![min.into(), preferred.into(), max.into()])
+    }
+
+    fn term(factor : f64, atom : &str, is_function : bool) -> Calc {
+        Calc {
+            terms : vec![Term {
+                factor,
+                atom : String::from(atom),
+                is_function,
+            }],
+        }
+    }
+
+    fn scaled(mut self, factor : f64) -> Calc {
+        for term in &mut self.terms {
+            term.factor *= factor;
+        }
+        self
+    }
+
+    fn plus(mut self, other : Calc) -> Calc {
+        for term in other.terms {
+            match self.terms.iter_mut().find(|existing| existing.atom == term.atom) {
+                Some(existing) => existing.factor += term.factor,
+                None => self.terms.push(term),
+            }
+        }
+        // Compared as rendered, as sums such as `0.1 + 0.2 - 0.3` leave a float error behind.
+        self.terms.retain(|term| number(term.factor) != "0");
+        self
+    }
+
+    // The expression without a `calc()` around it, as used within math functions.
+    fn expression(&self) -> String {
+        let mut expression = String::new();
+        for (index, term) in self.terms.iter().enumerate() {
+            let factor = match index {
+                0 => term.factor,
+                _ if term.factor < 0.0 => {
+                    expression.push_str(" - ");
+                    -term.factor
+                },
+                _ => {
+                    expression.push_str(" + ");
+                    term.factor
+                },
+            };
+            if !term.is_function && !factor.is_finite() {
+                expression.push_str(&format!("{} * 1{}", number(factor), term.atom));
+            }
+            else if !term.is_function {
+                expression.push_str(&format!("{}{}", number(factor), term.atom));
+            }
+            else if factor == 1.0 {
+                expression.push_str(&term.atom);
+            }
+            else {
+                expression.push_str(&format!("{} * {}", number(factor), term.atom));
+            }
+        }
+        if expression.is_empty() {
+            expression.push('0');
+        }
+        expression
+    }
+}
+fn function(name : &str, args : Vec<Calc>) -> Calc {
+    let args : Vec<String> = args.iter().map(|arg| arg.expression()).collect();
+    Calc::term(1.0, &format!("{}({})", name, args.join(", ")), true)
+}
+impl From<Length> for Calc {
+    fn from(length : Length) -> Calc {
+        Calc::term(length.value, length.unit, false)
+    }
+}
+impl From<Percentage> for Calc {
+    fn from(percentage : Percentage) -> Calc {
+        Calc::term(percentage.0, "%", false)
+    }
+}
+impl ToString for Calc {
+    fn to_string(&self) -> String {
+        match self.terms.as_slice() {
+            [] => String::from("0"),
+            [term] if term.factor.is_finite() && (!term.is_function || term.factor == 1.0) => self.expression(),
+            _ => format!("calc({})", self.expression()),
+        }
+    }
+}
+impl CssValue for Calc {}
+
+// Writes a number without a trailing `.0`, and without float error such as `0.30000000000000004`.
+// Dividing by zero gives CSS's own `infinity`, `-infinity` or `NaN`, which are only valid within
+// a math function.
+fn number(value : f64) -> String {
+    if value.is_nan() {
+        return String::from("NaN");
+    }
+    if value.is_infinite() {
+        return String::from(if value > 0.0 { "infinity" } else { "-infinity" });
+    }
+    let rounded = format!("{:.4}", value);
+    let trimmed = rounded.trim_end_matches('0').trim_end_matches('.');
+    match trimmed {
+        "-0" => String::from("0"),
+        _ => String::from(trimmed),
+    }
+}
+
+// Number with a unit, such as `4px`, or `calc(infinity * 1px)` if it isn't finite.
+fn dimension(value : f64, unit : &str) -> String {
+    match value.is_finite() {
+        true => format!("{}{}", number(value), unit),
+        false => format!("calc({} * 1{})", number(value), unit),
+    }
+}
+
+// Addition and subtraction of any lengths, percentages and expressions give an expression.
+macro_rules! sums {
+    ($($t:ty),*) => {
+        $(
+            impl<R : Into<Calc>> Add<R> for $t {
+                type Output = Calc;
+
+                fn add(self, other : R) -> Calc {
+                    Calc::from(self).plus(other.into())
+                }
+            }
+
+            impl<R : Into<Calc>> Sub<R> for $t {
+                type Output = Calc;
+
+                fn sub(self, other : R) -> Calc {
+                    Calc::from(self).plus(other.into().scaled(-1.0))
+                }
+            }
+        )*
+    }
+}
+sums!(Length, Percentage, Calc);
+
+// Scaling keeps the type, so a length multiplied by a number is still a length.
+macro_rules! scaling {
+    ($($t:ty => |$value:ident, $factor:ident| $scaled:expr),*) => {
+        $(
+            impl Mul<f64> for $t {
+                type Output = $t;
+
+                fn mul(self, $factor : f64) -> $t {
+                    let $value = self;
+                    $scaled
+                }
+            }
+
+            impl Div<f64> for $t {
+                type Output = $t;
+
+                fn div(self, divisor : f64) -> $t {
+                    self * (1.0 / divisor)
+                }
+            }
+
+            impl Neg for $t {
+                type Output = $t;
+
+                fn neg(self) -> $t {
+                    self * -1.0
+                }
+            }
+        )*
+    }
+}
+scaling!(
+    Length => |length, factor| Length { value : length.value * factor, unit : length.unit },
+    Percentage => |percentage, factor| Percentage(percentage.0 * factor),
+    Calc => |calc, factor| calc.scaled(factor)
+);
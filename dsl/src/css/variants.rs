@@ -0,0 +1,80 @@
+use crate::css::{Style, StyleItem};
+
+#[derive(Clone)]
+pub(crate) enum Condition {
+    // Appended to the selector of the style, such as `:hover` or `::before`.
+    Selector(String),
+    Media(String),
+}
+
+// Style which only applies in some state or context. These can't be written inline, so they are
+// rendered by `rule`, or moved into a stylesheet by `hoist_variants`.
+pub struct Variant {
+    pub(crate) condition : Condition,
+    pub(crate) style : Style,
+}
+impl StyleItem for Variant {
+    fn add_to(self, style : &mut Style) {
+        style.variants.push(self);
+    }
+}
+
+// Any pseudo-class or pseudo-element, such as `pseudo(":nth-child(2n)", style![...])`.
+pub fn pseudo(selector : &str, style : Style) -> Variant {
+    Variant {
+        condition : Condition::Selector(String::from(selector)),
+        style,
+    }
+}
+
+// Named so as not to clash with the `media` attribute when both modules are glob imported.
+pub fn media_query(query : &str, style : Style) -> Variant {
+    Variant {
+        condition : Condition::Media(String::from(query)),
+        style,
+    }
+}
+
+pub fn hover(style : Style) -> Variant {
+    pseudo(":hover", style)
+}
+
+pub fn focus(style : Style) -> Variant {
+    pseudo(":focus", style)
+}
+
+pub fn focus_visible(style : Style) -> Variant {
+    pseudo(":focus-visible", style)
+}
+
+pub fn focus_within(style : Style) -> Variant {
+    pseudo(":focus-within", style)
+}
+
+pub fn active(style : Style) -> Variant {
+    pseudo(":active", style)
+}
+
+pub fn visited(style : Style) -> Variant {
+    pseudo(":visited", style)
+}
+
+pub fn first_child(style : Style) -> Variant {
+    pseudo(":first-child", style)
+}
+
+pub fn last_child(style : Style) -> Variant {
+    pseudo(":last-child", style)
+}
+
+pub fn before(style : Style) -> Variant {
+    pseudo("::before", style)
+}
+
+pub fn after(style : Style) -> Variant {
+    pseudo("::after", style)
+}
+
+pub fn dark(style : Style) -> Variant {
+    media_query("(prefers-color-scheme: dark)", style)
+}
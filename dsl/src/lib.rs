@@ -4,14 +4,37 @@ fn remove_whitespace(string : &str) -> String {
     string.chars().filter(|c| !c.is_whitespace()).collect()
 }
 
+// FNV-1a, used for generated names which need to be stable between builds.
+fn hash(string : &str) -> u64 {
+    string.bytes().fold(0xcbf29ce484222325, |hash, byte| (hash ^ byte as u64).wrapping_mul(0x100000001b3))
+}
+
 pub mod attr {
     use crate::remove_whitespace;
     use proc_macros::*;
 
-    pub trait Attribute : ToString {}
+    pub mod class_list;
+    pub use class_list::*;
+
+    pub trait Attribute : ToString {
+        fn name(&self) -> String;
+
+        fn value(&self) -> String;
+    }
 
     all_attributes!();
 
+    // Class name generated by `css_module!` or `include_css!`, used wherever a `&str` is.
+    #[derive(Clone, Copy)]
+    pub struct ClassName(pub &'static str);
+    impl std::ops::Deref for ClassName {
+        type Target = str;
+
+        fn deref(&self) -> &str {
+            self.0
+        }
+    }
+
     pub struct Custom {
         attr : String,
         value : String,
@@ -27,7 +50,15 @@ pub mod attr {
             format!("{}=\"{}\"", self.attr, self.value)
         }
     }
-    impl Attribute for Custom {}
+    impl Attribute for Custom {
+        fn name(&self) -> String {
+            self.attr.clone()
+        }
+
+        fn value(&self) -> String {
+            self.value.clone()
+        }
+    }
 
 }
 
@@ -39,7 +70,41 @@ pub mod nodes {
     use crate::css::Style;
     use crate::attr::Attribute;
 
-    pub trait Node : ToString {}
+    pub trait Node : ToString {
+        fn as_element(&self) -> std::option::Option<&dyn Element> {
+            None
+        }
+
+        fn as_element_mut(&mut self) -> std::option::Option<&mut dyn Element> {
+            None
+        }
+
+        fn as_text(&self) -> std::option::Option<&Text> {
+            None
+        }
+
+        fn as_text_mut(&mut self) -> std::option::Option<&mut Text> {
+            None
+        }
+    }
+
+    // Common view of the generated elements, for passes over an already built tree.
+    pub trait Element : Node {
+        fn tag_name(&self) -> &'static str;
+
+        fn attributes(&self) -> &[Box<dyn Attribute>];
+
+        fn attributes_mut(&mut self) -> &mut Vec<Box<dyn Attribute>>;
+
+        fn css_props(&self) -> std::option::Option<&Style>;
+
+        fn css_props_mut(&mut self) -> &mut std::option::Option<Style>;
+
+        // `None` for void elements, which cannot have children.
+        fn children(&self) -> std::option::Option<&[Box<dyn Node>]>;
+
+        fn children_mut(&mut self) -> std::option::Option<&mut Vec<Box<dyn Node>>>;
+    }
 
     pub trait ParentNode : Node {
         fn child<N>(&mut self, child : N)
@@ -57,6 +122,27 @@ pub mod nodes {
 
     all_nodes!();
 
+    pub mod selector;
+    pub use selector::*;
+
+    pub mod visit;
+    pub use visit::*;
+
+    pub mod edit;
+    pub use edit::*;
+
+    // Calls `f` on every element of the tree, parents before their children.
+    pub(crate) fn for_each_element_mut(node : &mut dyn Node, f : &mut dyn FnMut(&mut dyn Element)) {
+        if let Some(element) = node.as_element_mut() {
+            f(element);
+            if let Some(children) = element.children_mut() {
+                for child in children {
+                    for_each_element_mut(child.as_mut(), f);
+                }
+            }
+        }
+    }
+
     // Text primitive
     pub struct Text {
         value : String
@@ -72,8 +158,24 @@ pub mod nodes {
                 value : String::from(value)
             }
         }
+
+        pub fn value(&self) -> &str {
+            &self.value
+        }
+
+        pub fn set_value(&mut self, value : &str) {
+            self.value = String::from(value);
+        }
+    }
+    impl Node for Text {
+        fn as_text(&self) -> std::option::Option<&Text> {
+            Some(self)
+        }
+
+        fn as_text_mut(&mut self) -> std::option::Option<&mut Text> {
+            Some(self)
+        }
     }
-    impl Node for Text {}
     #[macro_export]
     macro_rules! text {
         [$a:expr] => {
@@ -87,19 +189,156 @@ pub mod css {
     use proc_macros::*;
     use crate::remove_whitespace;
 
-    pub trait CssProp : ToString {}
+    pub use proc_macros::{css_module, include_css, known_classes};
+
+    pub mod values;
+    pub use values::*;
+
+    pub mod stylesheet;
+    pub use stylesheet::*;
+
+    pub mod variants;
+    pub use variants::*;
+
+    pub mod at_rules;
+    pub use at_rules::*;
+
+    pub mod hoist;
+    pub use hoist::*;
+
+    pub mod atomic;
+    pub use atomic::*;
+
+    pub mod parse;
+    pub use parse::*;
+
+    pub mod prefix;
+    pub use prefix::*;
+
+    pub mod theme;
+    pub use theme::*;
+
+    pub mod grid;
+    pub use grid::*;
+
+    pub mod critical;
+    pub use critical::*;
+
+    pub mod responsive;
+    pub use responsive::*;
+
+    // Not re-exported, as utilities are written with their module, such as `tw::flex()`.
+    pub mod tw;
+
+    pub mod shorthand;
+
+    pub trait CssProp : ToString {
+        fn name(&self) -> String;
+
+        fn value(&self) -> String;
+
+        fn important(self) -> Important<Self>
+            where Self : Sized {
+            Important(self)
+        }
+
+        // Values for wider viewports, as pairs of media query and value, from a `Responsive` value.
+        fn responsive(&self) -> Vec<(String, String)> {
+            Vec::new()
+        }
+    }
 
     all_css_props!();
 
-    pub struct Style(pub (in crate) Vec<Box<dyn CssProp + 'static>>);
+    // Declaration of a custom property, such as `--brand`, which can be read with `var`.
+    pub struct CustomProp {
+        name : String,
+        value : String,
+        responsive : Vec<(String, String)>,
+    }
+    pub fn custom_prop<V>(name : &str, value : V) -> CustomProp
+        where V : CssValue {
+        CustomProp {
+            name : String::from(name),
+            value : value.to_string(),
+            responsive : value.responsive(),
+        }
+    }
+    impl ToString for CustomProp {
+        fn to_string(&self) -> String {
+            format!("{}: {};", self.name, self.value)
+        }
+    }
+    impl CssProp for CustomProp {
+        fn name(&self) -> String {
+            self.name.clone()
+        }
+
+        fn value(&self) -> String {
+            self.value.clone()
+        }
+
+        fn responsive(&self) -> Vec<(String, String)> {
+            self.responsive.clone()
+        }
+    }
+
+    // Any property flagged with `!important`, from `CssProp::important`.
+    pub struct Important<P : CssProp>(P);
+    impl<P : CssProp> ToString for Important<P> {
+        fn to_string(&self) -> String {
+            format!("{}: {};", self.name(), self.value())
+        }
+    }
+    impl<P : CssProp> CssProp for Important<P> {
+        fn name(&self) -> String {
+            self.0.name()
+        }
+
+        fn value(&self) -> String {
+            format!("{} !important", self.0.value())
+        }
+
+        fn responsive(&self) -> Vec<(String, String)> {
+            self.0.responsive().into_iter().map(|(query, value)| (query, format!("{} !important", value))).collect()
+        }
+    }
+
+    pub struct Style {
+        pub (in crate) props : Vec<Box<dyn CssProp + 'static>>,
+        pub (in crate) variants : Vec<Variant>,
+    }
     impl Style {
         pub fn new() -> Style {
-            Style(Vec::new())
+            Style {
+                props : Vec::new(),
+                variants : Vec::new(),
+            }
+        }
+
+        pub fn with_prop<P>(&mut self, item : P)
+            where P : StyleItem {
+            item.add_to(self);
+        }
+    }
+    // Renders only the properties, as variants have no inline form, with complete sets of
+    // longhands collapsed into their shorthand.
+    impl ToString for Style {
+        fn to_string(&self) -> String {
+            shorthand::collapsed(&self.props)
         }
+    }
 
-        pub fn with_prop<P : 'static>(&mut self, item : P)
-            where P : CssProp {
-            self.0.push(Box::new(item));
+    // Anything which can be added to a `Style`, being any property, or a `Variant`.
+    pub trait StyleItem {
+        fn add_to(self, style : &mut Style);
+    }
+    // Properties with a `Responsive` value also add a media variant for each breakpoint.
+    impl<P : CssProp + 'static> StyleItem for P {
+        fn add_to(self, style : &mut Style) {
+            let (name, responsive) = (self.name(), self.responsive());
+            style.props.push(Box::new(self));
+            add_responsive(style, &name, responsive);
         }
     }
 
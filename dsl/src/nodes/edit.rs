@@ -0,0 +1,85 @@
+use crate::attr::Attribute;
+use crate::nodes::{Element, Node};
+
+// Changes to an already built element, such as injecting a field into every form. Implemented for
+// every element, including `dyn Element`, so it works within passes over a tree.
+pub trait EditableElement : Element {
+    // Value of the first attribute with the name.
+    fn get_attribute(&self, name : &str) -> Option<String> {
+        self.attributes()
+        .iter()
+        .find(|attribute| attribute.name().eq_ignore_ascii_case(name))
+        .map(|attribute| attribute.value())
+    }
+
+    // Replaces any attributes of the same name, or adds it.
+    fn set_attribute<A>(&mut self, attribute : A)
+        where A : Attribute + 'static {
+        let name = attribute.name();
+        let attributes = self.attributes_mut();
+        // Nothing before the first attribute of the same name is removed, so it keeps its place.
+        let index = attributes.iter().position(|existing| existing.name().eq_ignore_ascii_case(&name));
+        attributes.retain(|existing| !existing.name().eq_ignore_ascii_case(&name));
+        match index {
+            Some(index) => attributes.insert(index, Box::new(attribute)),
+            None => attributes.push(Box::new(attribute)),
+        }
+    }
+
+    // Removes every attribute with the name, returning whether there were any.
+    fn remove_attribute(&mut self, name : &str) -> bool {
+        let attributes = self.attributes_mut();
+        let count = attributes.len();
+        attributes.retain(|attribute| !attribute.name().eq_ignore_ascii_case(name));
+        attributes.len() != count
+    }
+
+    // Inserts a child before the one at `index`, or at the end when `index` is the number of
+    // children. Returns whether it was inserted, which it isn't if `index` is greater than that or
+    // the element is void.
+    fn insert_child<N>(&mut self, index : usize, child : N) -> bool
+        where N : Node + 'static {
+        match self.children_mut() {
+            Some(children) if index <= children.len() => {
+                children.insert(index, Box::new(child));
+                true
+            },
+            _ => false,
+        }
+    }
+
+    // Removes and returns the child at `index`, if there is one.
+    fn remove_child(&mut self, index : usize) -> Option<Box<dyn Node>> {
+        match self.children_mut() {
+            Some(children) if index < children.len() => Some(children.remove(index)),
+            _ => None,
+        }
+    }
+
+    // Replaces the child at `index`, returning the old one, or `None` and leaving the element
+    // unchanged if there is no child at `index`.
+    fn replace_child<N>(&mut self, index : usize, child : N) -> Option<Box<dyn Node>>
+        where N : Node + 'static {
+        match self.children_mut().and_then(|children| children.get_mut(index)) {
+            Some(existing) => Some(std::mem::replace(existing, Box::new(child))),
+            None => None,
+        }
+    }
+
+    // Keeps only the children for which `f` returns `true`.
+    fn retain_children<F>(&mut self, mut f : F)
+        where F : FnMut(&dyn Node) -> bool {
+        if let Some(children) = self.children_mut() {
+            children.retain(|child| f(child.as_ref()));
+        }
+    }
+
+    // Removes and returns every child.
+    fn take_children(&mut self) -> Vec<Box<dyn Node>> {
+        match self.children_mut() {
+            Some(children) => std::mem::take(children),
+            None => Vec::new(),
+        }
+    }
+}
+impl<E : Element + ?Sized> EditableElement for E {}
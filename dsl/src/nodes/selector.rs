@@ -0,0 +1,844 @@
+use std::fmt;
+
+use crate::nodes::{Element, Node};
+
+#[derive(Debug, PartialEq)]
+pub enum SelectorError {
+    // Selector, or part of a selector list, with nothing in it.
+    Empty,
+    Unexpected(char),
+    UnknownPseudoClass(String),
+    // Invalid argument to a pseudo-class such as `:nth-child`.
+    InvalidArgument(String),
+    // Unclosed string, attribute selector or bracket.
+    Unterminated,
+}
+impl fmt::Display for SelectorError {
+    fn fmt(&self, f : &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SelectorError::Empty => write!(f, "empty selector"),
+            SelectorError::Unexpected(c) => write!(f, "unexpected `{}` in selector", c),
+            SelectorError::UnknownPseudoClass(name) => write!(f, "unknown pseudo-class `:{}`", name),
+            SelectorError::InvalidArgument(argument) => write!(f, "invalid pseudo-class argument `{}`", argument),
+            SelectorError::Unterminated => write!(f, "unterminated string, attribute selector or bracket"),
+        }
+    }
+}
+impl std::error::Error for SelectorError {}
+
+// Parsed CSS selector list, for matching against the elements of a tree. Pseudo-classes which
+// depend on the state of the page, such as `:hover`, and pseudo-elements, such as `::before`, never
+// match when querying the tree, as nothing in it is hovered. Critical CSS instead keeps every
+// selector which they could make match, so both `a:hover` and `a:not(:hover)` are kept for an `a`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Selector {
+    complexes : Vec<Complex>,
+}
+
+// Compounds joined by combinators, where `combinators[i]` is between `compounds[i]` and
+// `compounds[i + 1]`.
+#[derive(Clone, Debug, PartialEq)]
+struct Complex {
+    compounds : Vec<Compound>,
+    combinators : Vec<Combinator>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Combinator {
+    Descendant,
+    Child,
+    NextSibling,
+    SubsequentSibling,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Compound {
+    // Lower case tag name, or `None` for `*` or no type selector.
+    tag : Option<String>,
+    simples : Vec<Simple>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Simple {
+    Id(String),
+    Class(String),
+    Attribute {
+        name : String,
+        operator : Option<(char, String)>,
+        case_insensitive : bool,
+    },
+    Nth {
+        a : i64,
+        b : i64,
+        of_type : bool,
+        from_end : bool,
+    },
+    Root,
+    Empty,
+    Not(Vec<Complex>),
+    Is(Vec<Complex>),
+    // Relative selectors, each starting with an `Anchor` compound for the element itself, so that
+    // `:has(> a)` is held as `Anchor > a`.
+    Has(Vec<Complex>),
+    Anchor,
+    // State or pseudo-element, which the tree alone can't tell.
+    State,
+}
+
+// Pseudo-classes which depend on the state of the page rather than the tree.
+const STATE_PSEUDO_CLASSES : &[&str] = &[
+    "hover", "focus", "focus-visible", "focus-within", "active", "visited", "link", "any-link",
+    "target", "target-within", "placeholder-shown", "autofill", "default", "valid", "invalid",
+    "in-range", "out-of-range", "user-valid", "user-invalid", "indeterminate", "fullscreen",
+    "modal", "popover-open", "open", "closed", "playing", "paused", "defined",
+];
+
+// Elements which can be disabled, the only ones `:enabled` and `:disabled` match. Those within a
+// disabled `<fieldset>` aren't taken to be disabled.
+const FORM_CONTROLS : &str = "button, input, select, textarea, fieldset, option, optgroup";
+
+// Elements the user can edit, which `:read-write` matches, and `:read-only` matches every other.
+const EDITABLE : &str = "textarea:not([readonly], [disabled]), \
+    input:not([readonly], [disabled], [type=checkbox i], [type=radio i], [type=button i], [type=submit i], \
+    [type=reset i], [type=image i], [type=file i], [type=hidden i], [type=range i], [type=color i]), \
+    [contenteditable]:not([contenteditable=false i])";
+
+// Legacy pseudo-elements, which may be written with a single colon.
+const LEGACY_PSEUDO_ELEMENTS : &[&str] = &["before", "after", "first-line", "first-letter"];
+
+// Whether an element matches, where `Maybe` depends on state the tree doesn't have. `Maybe` stays
+// `Maybe` through `:not`, so that `:not(:hover)` isn't taken to never match.
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+enum Truth {
+    No,
+    Maybe,
+    Yes,
+}
+impl Truth {
+    fn not(self) -> Truth {
+        match self {
+            Truth::No => Truth::Yes,
+            Truth::Maybe => Truth::Maybe,
+            Truth::Yes => Truth::No,
+        }
+    }
+}
+impl From<bool> for Truth {
+    fn from(value : bool) -> Truth {
+        match value {
+            true => Truth::Yes,
+            false => Truth::No,
+        }
+    }
+}
+
+// Most certain of the results, stopping at the first `Yes`.
+fn any<I>(results : I) -> Truth
+    where I : IntoIterator<Item = Truth> {
+    let mut best = Truth::No;
+    for result in results {
+        if result == Truth::Yes {
+            return Truth::Yes;
+        }
+        if result > best {
+            best = result;
+        }
+    }
+    best
+}
+
+// Least certain of the results, stopping at the first `No`.
+fn all<I>(results : I) -> Truth
+    where I : IntoIterator<Item = Truth> {
+    let mut worst = Truth::Yes;
+    for result in results {
+        if result == Truth::No {
+            return Truth::No;
+        }
+        if result < worst {
+            worst = result;
+        }
+    }
+    worst
+}
+
+// What state pseudo-classes match, and the address of the element which the `Anchor` of a
+// relative selector stands for.
+#[derive(Clone, Copy)]
+struct Context {
+    state : Truth,
+    anchor : Option<*const ()>,
+}
+
+fn address(element : &dyn Element) -> *const () {
+    element as *const dyn Element as *const ()
+}
+
+impl Selector {
+    pub fn parse(selector : &str) -> Result<Selector, SelectorError> {
+        let mut parser = Parser {
+            chars : selector.chars().collect(),
+            index : 0,
+        };
+        let complexes = parser.selector_list()?;
+        match parser.peek() {
+            Some(c) => Err(SelectorError::Unexpected(c)),
+            None => Ok(Selector { complexes }),
+        }
+    }
+
+    // Whether any element of the tree matches.
+    pub fn matches_any(&self, root : &dyn Node) -> bool {
+        self.find(root).is_some()
+    }
+
+    // Whether any element of the tree could match, given some state of the page.
+    pub(crate) fn may_match_any(&self, root : &dyn Node) -> bool {
+        let mut found = false;
+        visit(root, None, &mut Vec::new(), &mut |path| {
+            found = self.test(path, Truth::Maybe) != Truth::No;
+            found
+        });
+        found
+    }
+
+    // First matching element of the tree, in document order.
+    pub fn find<'a>(&self, root : &'a dyn Node) -> Option<&'a dyn Element> {
+        let mut found = None;
+        visit(root, None, &mut Vec::new(), &mut |path| {
+            if self.matches_path(path) {
+                found = path.last().map(|position| position.element);
+            }
+            found.is_some()
+        });
+        found
+    }
+
+    // Every matching element of the tree, in document order.
+    pub fn find_all<'a>(&self, root : &'a dyn Node) -> Vec<&'a dyn Element> {
+        let mut found = Vec::new();
+        visit(root, None, &mut Vec::new(), &mut |path| {
+            if self.matches_path(path) {
+                found.push(path.last().unwrap().element);
+            }
+            false
+        });
+        found
+    }
+
+    // Calls `f` on every matching element of the tree. Elements are called in reverse document
+    // order, so that changes `f` makes to an element's children don't move elements it is still
+    // to be called on.
+    pub fn for_each_match_mut(&self, root : &mut dyn Node, f : &mut dyn FnMut(&mut dyn Element)) {
+        // Path of child indices from the root to each match.
+        let mut matches : Vec<Vec<usize>> = Vec::new();
+        visit(&*root, None, &mut Vec::new(), &mut |path| {
+            if self.matches_path(path) {
+                matches.push(path.iter().filter_map(|position| position.siblings.map(|(_, index)| index)).collect());
+            }
+            false
+        });
+
+        'matches: for indices in matches.into_iter().rev() {
+            let mut node : &mut dyn Node = &mut *root;
+            for index in indices {
+                node = match node.as_element_mut().and_then(|element| element.children_mut()).and_then(|children| children.get_mut(index)) {
+                    Some(child) => child.as_mut(),
+                    None => continue 'matches,
+                };
+            }
+            if let Some(element) = node.as_element_mut() {
+                f(element);
+            }
+        }
+    }
+
+    fn matches_path(&self, path : &[Position]) -> bool {
+        self.test(path, Truth::No) == Truth::Yes
+    }
+
+    fn test(&self, path : &[Position], state : Truth) -> Truth {
+        matches_list(&self.complexes, path, Context { state, anchor : None })
+    }
+}
+
+// First element of the tree matching the selector, such as `find(&page, "nav > ul li.active a")`.
+pub fn find<'a>(root : &'a dyn Node, selector : &str) -> Result<Option<&'a dyn Element>, SelectorError> {
+    Ok(Selector::parse(selector)?.find(root))
+}
+
+// Every element of the tree matching the selector, in document order.
+pub fn find_all<'a>(root : &'a dyn Node, selector : &str) -> Result<Vec<&'a dyn Element>, SelectorError> {
+    Ok(Selector::parse(selector)?.find_all(root))
+}
+
+// Element within the tree, with the children of its parent for sibling selectors, or `None` for
+// the root of the tree.
+#[derive(Clone, Copy)]
+struct Position<'a> {
+    element : &'a dyn Element,
+    siblings : Option<(&'a [Box<dyn Node>], usize)>,
+}
+
+// Calls `f` with the path from the root to every element of the tree, parents before their
+// children, until it returns `true`. Returns whether it did.
+fn visit<'a>(
+    node : &'a dyn Node,
+    siblings : Option<(&'a [Box<dyn Node>], usize)>,
+    path : &mut Vec<Position<'a>>,
+    f : &mut dyn FnMut(&[Position<'a>]) -> bool,
+) -> bool {
+    let element = match node.as_element() {
+        Some(element) => element,
+        None => return false,
+    };
+
+    path.push(Position { element, siblings });
+    let mut stop = f(path);
+    if let Some(children) = element.children() {
+        for index in 0..children.len() {
+            if stop {
+                break;
+            }
+            stop = visit(children[index].as_ref(), Some((children, index)), path, f);
+        }
+    }
+    path.pop();
+    stop
+}
+
+// Elements which share a parent with the last element of the path, and its index among them.
+fn element_siblings<'a>(position : &Position<'a>) -> (Vec<&'a dyn Element>, usize) {
+    match position.siblings {
+        Some((siblings, index)) => {
+            let before = siblings[..index].iter().filter(|node| node.as_element().is_some()).count();
+            (siblings.iter().filter_map(|node| node.as_element()).collect(), before)
+        },
+        None => (vec![position.element], 0),
+    }
+}
+
+fn matches_list(complexes : &[Complex], path : &[Position], context : Context) -> Truth {
+    any(complexes.iter().map(|complex| matches_complex(complex, complex.compounds.len() - 1, path, context)))
+}
+
+// Matches right to left, from the compound at `index` against the last element of the path.
+fn matches_complex(complex : &Complex, index : usize, path : &[Position], context : Context) -> Truth {
+    let position = match path.last() {
+        Some(position) => position,
+        None => return Truth::No,
+    };
+    let compound = matches_compound(&complex.compounds[index], path, context);
+    if compound == Truth::No || index == 0 {
+        return compound;
+    }
+
+    let ancestors = &path[..path.len() - 1];
+    let rest = match complex.combinators[index - 1] {
+        Combinator::Child => matches_complex(complex, index - 1, ancestors, context),
+        Combinator::Descendant => {
+            any((1..=ancestors.len()).rev().map(|end| matches_complex(complex, index - 1, &ancestors[..end], context)))
+        },
+        Combinator::NextSibling | Combinator::SubsequentSibling => {
+            let (siblings, sibling_index) = match position.siblings {
+                Some((siblings, index)) => (siblings, index),
+                None => return Truth::No,
+            };
+            let previous = (0..sibling_index).rev().filter(|i| siblings[*i].as_element().is_some());
+            let previous : Vec<usize> = match complex.combinators[index - 1] {
+                Combinator::NextSibling => previous.take(1).collect(),
+                _ => previous.collect(),
+            };
+            any(previous.into_iter().map(|i| {
+                let mut sibling_path = ancestors.to_vec();
+                sibling_path.push(Position {
+                    element : siblings[i].as_element().unwrap(),
+                    siblings : Some((siblings, i)),
+                });
+                matches_complex(complex, index - 1, &sibling_path, context)
+            }))
+        },
+    };
+    all([compound, rest])
+}
+
+fn matches_compound(compound : &Compound, path : &[Position], context : Context) -> Truth {
+    let position = path.last().unwrap();
+    let element = position.element;
+    if let Some(tag) = &compound.tag {
+        if !element.tag_name().eq_ignore_ascii_case(tag) {
+            return Truth::No;
+        }
+    }
+    all(compound.simples.iter().map(|simple| matches_simple(simple, position, path, context)))
+}
+
+fn attribute_values(element : &dyn Element, name : &str) -> Vec<String> {
+    element
+    .attributes()
+    .iter()
+    .filter(|attribute| attribute.name().eq_ignore_ascii_case(name))
+    .map(|attribute| attribute.value())
+    .collect()
+}
+
+fn matches_simple<'a>(simple : &Simple, position : &Position<'a>, path : &[Position<'a>], context : Context) -> Truth {
+    let element = position.element;
+    match simple {
+        Simple::Id(id) => attribute_values(element, "id").iter().any(|value| value == id).into(),
+        Simple::Class(class) => {
+            attribute_values(element, "class").iter().any(|value| value.split_whitespace().any(|c| c == class)).into()
+        },
+        Simple::Attribute { name, operator, case_insensitive } => {
+            attribute_values(element, name).iter().any(|value| match operator {
+                None => true,
+                Some((operator, expected)) => {
+                    let (value, expected) = match case_insensitive {
+                        true => (value.to_lowercase(), expected.to_lowercase()),
+                        false => (value.clone(), expected.clone()),
+                    };
+                    match operator {
+                        '=' => value == expected,
+                        '~' => value.split_whitespace().any(|word| word == expected),
+                        '|' => value == expected || value.starts_with(&format!("{}-", expected)),
+                        '^' => !expected.is_empty() && value.starts_with(&expected),
+                        '$' => !expected.is_empty() && value.ends_with(&expected),
+                        '*' => !expected.is_empty() && value.contains(&expected),
+                        _ => false,
+                    }
+                },
+            }).into()
+        },
+        Simple::Nth { a, b, of_type, from_end } => {
+            let (siblings, index) = element_siblings(position);
+            let mut siblings : Vec<&dyn Element> = siblings;
+            let mut index = index;
+            if *of_type {
+                let before = siblings[..index].iter().filter(|sibling| sibling.tag_name() == element.tag_name()).count();
+                siblings.retain(|sibling| sibling.tag_name() == element.tag_name());
+                index = before;
+            }
+            let n = match from_end {
+                true => (siblings.len() - index) as i64,
+                false => index as i64 + 1,
+            };
+            match a {
+                0 => n == *b,
+                _ => (n - b) % a == 0 && (n - b) / a >= 0,
+            }.into()
+        },
+        Simple::Root => position.siblings.is_none().into(),
+        Simple::Empty => {
+            element.children().unwrap_or(&[]).iter().all(|child| child.as_element().is_none() && child.to_string().is_empty()).into()
+        },
+        Simple::Not(complexes) => matches_list(complexes, path, context).not(),
+        Simple::Is(complexes) => matches_list(complexes, path, context),
+        Simple::Has(complexes) => {
+            // Relative selectors can only match within the element or its later siblings, which
+            // are visited with the real path to them, so the `Anchor` can find the element.
+            let context = Context {
+                anchor : Some(address(element)),
+                ..context
+            };
+            let mut result = Truth::No;
+            let mut check = |path : &[Position]| {
+                result = any([result, matches_list(complexes, path, context)]);
+                result == Truth::Yes
+            };
+            if let Some(children) = element.children() {
+                let mut prefix = path.to_vec();
+                for index in 0..children.len() {
+                    if visit(children[index].as_ref(), Some((children, index)), &mut prefix, &mut check) {
+                        return Truth::Yes;
+                    }
+                }
+            }
+            if let Some((siblings, index)) = position.siblings {
+                let mut prefix = path[..path.len() - 1].to_vec();
+                for index in index + 1..siblings.len() {
+                    if visit(siblings[index].as_ref(), Some((siblings, index)), &mut prefix, &mut check) {
+                        return Truth::Yes;
+                    }
+                }
+            }
+            result
+        },
+        Simple::Anchor => (context.anchor == Some(address(element))).into(),
+        Simple::State => context.state,
+    }
+}
+
+struct Parser {
+    chars : Vec<char>,
+    index : usize,
+}
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.index).copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek();
+        self.index += 1;
+        c
+    }
+
+    fn skip_whitespace(&mut self) -> bool {
+        let start = self.index;
+        while self.peek().is_some_and(|c| c.is_whitespace()) {
+            self.index += 1;
+        }
+        self.index > start
+    }
+
+    fn selector_list(&mut self) -> Result<Vec<Complex>, SelectorError> {
+        let mut complexes = vec![self.complex()?];
+        while self.peek() == Some(',') {
+            self.index += 1;
+            complexes.push(self.complex()?);
+        }
+        Ok(complexes)
+    }
+
+    fn complex(&mut self) -> Result<Complex, SelectorError> {
+        self.skip_whitespace();
+        let mut complex = Complex {
+            compounds : vec![self.compound()?],
+            combinators : Vec::new(),
+        };
+        loop {
+            let had_whitespace = self.skip_whitespace();
+            let combinator = match self.peek() {
+                Some('>') => Combinator::Child,
+                Some('+') => Combinator::NextSibling,
+                Some('~') => Combinator::SubsequentSibling,
+                Some(',') | Some(')') | None => break,
+                Some(_) if had_whitespace => {
+                    complex.combinators.push(Combinator::Descendant);
+                    complex.compounds.push(self.compound()?);
+                    continue;
+                },
+                Some(c) => return Err(SelectorError::Unexpected(c)),
+            };
+            self.index += 1;
+            self.skip_whitespace();
+            complex.combinators.push(combinator);
+            complex.compounds.push(self.compound()?);
+        }
+        Ok(complex)
+    }
+
+    fn compound(&mut self) -> Result<Compound, SelectorError> {
+        let mut compound = Compound {
+            tag : None,
+            simples : Vec::new(),
+        };
+        let start = self.index;
+
+        match self.peek() {
+            Some('*') => self.index += 1,
+            Some(c) if is_ident_start(c) => compound.tag = Some(self.ident()?.to_ascii_lowercase()),
+            _ => (),
+        }
+
+        loop {
+            match self.peek() {
+                Some('#') => {
+                    self.index += 1;
+                    compound.simples.push(Simple::Id(self.ident()?));
+                },
+                Some('.') => {
+                    self.index += 1;
+                    compound.simples.push(Simple::Class(self.ident()?));
+                },
+                Some('[') => {
+                    self.index += 1;
+                    compound.simples.push(self.attribute()?);
+                },
+                Some(':') => {
+                    self.index += 1;
+                    compound.simples.push(self.pseudo()?);
+                },
+                _ => break,
+            }
+        }
+
+        match self.index > start {
+            true => Ok(compound),
+            false => match self.peek() {
+                Some(c) if c != ',' && c != ')' => Err(SelectorError::Unexpected(c)),
+                _ => Err(SelectorError::Empty),
+            },
+        }
+    }
+
+    fn ident(&mut self) -> Result<String, SelectorError> {
+        let mut ident = String::new();
+        while let Some(c) = self.peek() {
+            if c == '\\' {
+                self.index += 1;
+                ident.push(self.next().ok_or(SelectorError::Unterminated)?);
+            }
+            else if is_ident_start(c) || c.is_ascii_digit() || c == '-' {
+                self.index += 1;
+                ident.push(c);
+            }
+            else {
+                break;
+            }
+        }
+        match ident.is_empty() {
+            true => match self.peek() {
+                Some(c) => Err(SelectorError::Unexpected(c)),
+                None => Err(SelectorError::Empty),
+            },
+            false => Ok(ident),
+        }
+    }
+
+    fn string(&mut self, quote : char) -> Result<String, SelectorError> {
+        let mut string = String::new();
+        loop {
+            match self.next() {
+                Some('\\') => string.push(self.next().ok_or(SelectorError::Unterminated)?),
+                Some(c) if c == quote => return Ok(string),
+                Some(c) => string.push(c),
+                None => return Err(SelectorError::Unterminated),
+            }
+        }
+    }
+
+    fn attribute(&mut self) -> Result<Simple, SelectorError> {
+        self.skip_whitespace();
+        if self.peek().is_none() {
+            return Err(SelectorError::Unterminated);
+        }
+        let name = self.ident()?.to_ascii_lowercase();
+        self.skip_whitespace();
+
+        let operator = match self.next() {
+            Some(']') => None,
+            Some('=') => Some('='),
+            Some(c) if "~|^$*".contains(c) && self.peek() == Some('=') => {
+                self.index += 1;
+                Some(c)
+            },
+            Some(c) => return Err(SelectorError::Unexpected(c)),
+            None => return Err(SelectorError::Unterminated),
+        };
+        let operator = match operator {
+            Some(operator) => operator,
+            None => {
+                return Ok(Simple::Attribute {
+                    name,
+                    operator : None,
+                    case_insensitive : false,
+                });
+            },
+        };
+
+        self.skip_whitespace();
+        let value = match self.peek() {
+            Some(quote) if quote == '"' || quote == '\'' => {
+                self.index += 1;
+                self.string(quote)?
+            },
+            _ => self.ident()?,
+        };
+        self.skip_whitespace();
+        let case_insensitive = match self.peek() {
+            Some('i') | Some('I') => {
+                self.index += 1;
+                true
+            },
+            Some('s') | Some('S') => {
+                self.index += 1;
+                false
+            },
+            _ => false,
+        };
+        self.skip_whitespace();
+        match self.next() {
+            Some(']') => Ok(Simple::Attribute {
+                name,
+                operator : Some((operator, value)),
+                case_insensitive,
+            }),
+            Some(c) => Err(SelectorError::Unexpected(c)),
+            None => Err(SelectorError::Unterminated),
+        }
+    }
+
+    // Argument of a functional pseudo-class, up to its closing bracket.
+    fn argument(&mut self) -> Result<String, SelectorError> {
+        let mut depth = 0;
+        let mut argument = String::new();
+        loop {
+            match self.next() {
+                Some('(') => depth += 1,
+                Some(')') if depth == 0 => return Ok(argument),
+                Some(')') => depth -= 1,
+                None => return Err(SelectorError::Unterminated),
+                _ => (),
+            }
+            argument.push(self.chars[self.index - 1]);
+        }
+    }
+
+    fn nested_list(&mut self) -> Result<Vec<Complex>, SelectorError> {
+        let complexes = self.selector_list()?;
+        self.skip_whitespace();
+        match self.next() {
+            Some(')') => Ok(complexes),
+            Some(c) => Err(SelectorError::Unexpected(c)),
+            None => Err(SelectorError::Unterminated),
+        }
+    }
+
+    // Selector list of `:has`, where each selector may start with a combinator, and is otherwise
+    // relative to the descendants.
+    fn relative_list(&mut self) -> Result<Vec<Complex>, SelectorError> {
+        let mut complexes = Vec::new();
+        loop {
+            self.skip_whitespace();
+            let combinator = match self.peek() {
+                Some('>') => Combinator::Child,
+                Some('+') => Combinator::NextSibling,
+                Some('~') => Combinator::SubsequentSibling,
+                _ => Combinator::Descendant,
+            };
+            if combinator != Combinator::Descendant {
+                self.index += 1;
+            }
+
+            let mut complex = self.complex()?;
+            complex.compounds.insert(0, Compound {
+                tag : None,
+                simples : vec![Simple::Anchor],
+            });
+            complex.combinators.insert(0, combinator);
+            complexes.push(complex);
+
+            match self.next() {
+                Some(',') => (),
+                Some(')') => return Ok(complexes),
+                Some(c) => return Err(SelectorError::Unexpected(c)),
+                None => return Err(SelectorError::Unterminated),
+            }
+        }
+    }
+
+    fn pseudo(&mut self) -> Result<Simple, SelectorError> {
+        // Pseudo-elements.
+        if self.peek() == Some(':') {
+            self.index += 1;
+            self.ident()?;
+            if self.peek() == Some('(') {
+                self.index += 1;
+                self.argument()?;
+            }
+            return Ok(Simple::State);
+        }
+
+        let name = self.ident()?.to_ascii_lowercase();
+        if self.peek() == Some('(') {
+            self.index += 1;
+            return match name.as_str() {
+                "not" => Ok(Simple::Not(self.nested_list()?)),
+                "is" | "where" | "matches" => Ok(Simple::Is(self.nested_list()?)),
+                "has" => Ok(Simple::Has(self.relative_list()?)),
+                "nth-child" | "nth-last-child" | "nth-of-type" | "nth-last-of-type" => {
+                    let argument = self.argument()?;
+                    let (a, b) = parse_nth(&argument).ok_or(SelectorError::InvalidArgument(argument))?;
+                    Ok(Simple::Nth {
+                        a,
+                        b,
+                        of_type : name.ends_with("of-type"),
+                        from_end : name.starts_with("nth-last"),
+                    })
+                },
+                "lang" | "dir" | "state" => {
+                    self.argument()?;
+                    Ok(Simple::State)
+                },
+                _ => Err(SelectorError::UnknownPseudoClass(name)),
+            };
+        }
+
+        let nth = |a, b, of_type, from_end| Ok(Simple::Nth { a, b, of_type, from_end });
+        match name.as_str() {
+            "first-child" => nth(0, 1, false, false),
+            "last-child" => nth(0, 1, false, true),
+            "first-of-type" => nth(0, 1, true, false),
+            "last-of-type" => nth(0, 1, true, true),
+            "only-child" => Ok(Simple::Is(vec![only(false)])),
+            "only-of-type" => Ok(Simple::Is(vec![only(true)])),
+            "root" => Ok(Simple::Root),
+            "empty" => Ok(Simple::Empty),
+            "checked" => Ok(Simple::Is(built_in("input[checked], option[selected]"))),
+            "disabled" => Ok(Simple::Is(built_in(&format!(":is({})[disabled]", FORM_CONTROLS)))),
+            "enabled" => Ok(Simple::Is(built_in(&format!(":is({}):not([disabled])", FORM_CONTROLS)))),
+            "required" => Ok(Simple::Is(built_in("input[required], select[required], textarea[required]"))),
+            "optional" => Ok(Simple::Is(built_in(":is(input, select, textarea):not([required])"))),
+            "read-write" => Ok(Simple::Is(built_in(EDITABLE))),
+            "read-only" => Ok(Simple::Not(built_in(EDITABLE))),
+            _ if STATE_PSEUDO_CLASSES.contains(&name.as_str()) => Ok(Simple::State),
+            _ if LEGACY_PSEUDO_ELEMENTS.contains(&name.as_str()) => Ok(Simple::State),
+            _ => Err(SelectorError::UnknownPseudoClass(name)),
+        }
+    }
+}
+
+// Selector list of a pseudo-class written in terms of others.
+fn built_in(selector : &str) -> Vec<Complex> {
+    Selector::parse(selector).expect("built in selectors are valid").complexes
+}
+
+fn compound_of(simples : Vec<Simple>) -> Complex {
+    Complex {
+        compounds : vec![Compound {
+            tag : None,
+            simples,
+        }],
+        combinators : Vec::new(),
+    }
+}
+
+// Both first and last, of type or of any element.
+fn only(of_type : bool) -> Complex {
+    compound_of(vec![
+        Simple::Nth { a : 0, b : 1, of_type, from_end : false },
+        Simple::Nth { a : 0, b : 1, of_type, from_end : true },
+    ])
+}
+
+fn is_ident_start(c : char) -> bool {
+    c.is_ascii_alphabetic() || c == '_' || c == '-' || !c.is_ascii()
+}
+
+// Parses the `an+b` argument of `:nth-child` and its relatives, such as `2n+1`, `-n+3` or `odd`.
+fn parse_nth(argument : &str) -> Option<(i64, i64)> {
+    let argument : String = argument.chars().filter(|c| !c.is_whitespace()).collect::<String>().to_ascii_lowercase();
+    match argument.as_str() {
+        "odd" => return Some((2, 1)),
+        "even" => return Some((2, 0)),
+        _ => (),
+    }
+
+    match argument.split_once('n') {
+        Some((a, b)) => {
+            let a = match a {
+                "" | "+" => 1,
+                "-" => -1,
+                _ => a.parse().ok()?,
+            };
+            let b = match b {
+                "" => 0,
+                _ if b.starts_with('+') || b.starts_with('-') => b.parse().ok()?,
+                _ => return None,
+            };
+            Some((a, b))
+        },
+        None => Some((0, argument.parse().ok()?)),
+    }
+}
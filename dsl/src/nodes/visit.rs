@@ -0,0 +1,104 @@
+use crate::nodes::{Element, Node, Text};
+
+// What a visitor wants to happen after it has visited an element.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Walk {
+    Continue,
+    // Carries on past the element's children without visiting them.
+    SkipChildren,
+    // Visits nothing more.
+    Stop,
+}
+
+// Analysis of a tree, such as collecting every link, walked with `walk`. Every method does
+// nothing by default, so a visitor only implements what it needs.
+pub trait Visitor {
+    // Called on each element before its children.
+    fn visit_element(&mut self, _element : &dyn Element) -> Walk {
+        Walk::Continue
+    }
+
+    // Called on each element after its children.
+    fn leave_element(&mut self, _element : &dyn Element) {}
+
+    fn visit_text(&mut self, _text : &Text) {}
+}
+
+// Transform of a tree, such as rewriting URLs, walked with `walk_mut`.
+pub trait VisitorMut {
+    // Called on each element before its children, so changes to its children are then visited.
+    fn visit_element(&mut self, _element : &mut dyn Element) -> Walk {
+        Walk::Continue
+    }
+
+    // Called on each element after its children.
+    fn leave_element(&mut self, _element : &mut dyn Element) {}
+
+    fn visit_text(&mut self, _text : &mut Text) {}
+}
+
+// Visits every node of the tree, parents before their children, in document order.
+pub fn walk<V>(node : &dyn Node, visitor : &mut V)
+    where V : Visitor + ?Sized {
+    walk_node(node, visitor);
+}
+
+fn walk_node<V>(node : &dyn Node, visitor : &mut V) -> Walk
+    where V : Visitor + ?Sized {
+    if let Some(text) = node.as_text() {
+        visitor.visit_text(text);
+        return Walk::Continue;
+    }
+    let element = match node.as_element() {
+        Some(element) => element,
+        None => return Walk::Continue,
+    };
+
+    match visitor.visit_element(element) {
+        Walk::Stop => return Walk::Stop,
+        Walk::SkipChildren => (),
+        Walk::Continue => {
+            for child in element.children().unwrap_or(&[]) {
+                if walk_node(child.as_ref(), visitor) == Walk::Stop {
+                    return Walk::Stop;
+                }
+            }
+        },
+    }
+    visitor.leave_element(element);
+    Walk::Continue
+}
+
+// Visits every node of the tree, parents before their children, in document order.
+pub fn walk_mut<V>(node : &mut dyn Node, visitor : &mut V)
+    where V : VisitorMut + ?Sized {
+    walk_node_mut(node, visitor);
+}
+
+fn walk_node_mut<V>(node : &mut dyn Node, visitor : &mut V) -> Walk
+    where V : VisitorMut + ?Sized {
+    if let Some(text) = node.as_text_mut() {
+        visitor.visit_text(text);
+        return Walk::Continue;
+    }
+    let element = match node.as_element_mut() {
+        Some(element) => element,
+        None => return Walk::Continue,
+    };
+
+    match visitor.visit_element(element) {
+        Walk::Stop => return Walk::Stop,
+        Walk::SkipChildren => (),
+        Walk::Continue => {
+            if let Some(children) = element.children_mut() {
+                for child in children {
+                    if walk_node_mut(child.as_mut(), visitor) == Walk::Stop {
+                        return Walk::Stop;
+                    }
+                }
+            }
+        },
+    }
+    visitor.leave_element(element);
+    Walk::Continue
+}
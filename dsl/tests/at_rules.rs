@@ -0,0 +1,23 @@
+// Checks that at-rules render, and that the properties referencing them use their names.
+
+use dsl::*;
+use dsl::css::*;
+
+#[test]
+fn keyframes_are_referenced_by_name() {
+    let spin = keyframes("spin").frame("from", style![rotate("0deg")]).frame("to", style![rotate("360deg")]);
+    let style = style![animation_name_of(spin.name()), animation_duration("1s")];
+    assert_eq!(style.to_string(), "animation-name: spin;animation-duration: 1s;");
+    assert_eq!(spin.to_string(), "@keyframes spin {from {rotate: 0deg;}to {rotate: 360deg;}}");
+}
+
+#[test]
+fn font_faces_are_referenced_by_family() {
+    let inter = font_face("Inter").src("/fonts/inter.woff2", "woff2").display("swap");
+    let style = style![font_family_of(inter.family().or("sans-serif"))];
+    assert_eq!(style.to_string(), "font-family: \"Inter\", sans-serif;");
+    assert_eq!(
+        inter.to_string(),
+        "@font-face {font-family: \"Inter\";src: url(\"/fonts/inter.woff2\") format(\"woff2\");font-display: swap;}"
+    );
+}
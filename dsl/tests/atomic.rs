@@ -0,0 +1,57 @@
+// Checks that replacing inline styles with atomic classes leaves every element styled as it was.
+
+use dsl::*;
+use dsl::css::*;
+use dsl::nodes::*;
+
+fn extract(mut root : Div) -> (String, String) {
+    let stylesheet = extract_atomic(&mut root);
+    (stylesheet.to_string(), root.to_string())
+}
+
+#[test]
+fn shares_classes_between_elements() {
+    let (css, html) = extract(div!([][
+        p!([][color("red"), margin("0")][]),
+        p!([][margin("0")][])
+    ]));
+    assert_eq!(css, ".a0 {color: red;}.a1 {margin: 0;}");
+    assert_eq!(html, "<div><p class=\"a0 a1\"></p><p class=\"a1\"></p></div>");
+}
+
+#[test]
+fn later_declaration_overrides() {
+    let (css, html) = extract(div!([][color("red"), color("blue")][]));
+    assert_eq!(css, ".a0 {color: blue;}");
+    assert_eq!(html, "<div class=\"a0\"></div>");
+}
+
+#[test]
+fn important_declaration_is_kept() {
+    let (css, html) = extract(div!([][color("red").important(), color("blue")][]));
+    assert_eq!(css, ".a0 {color: red !important;}");
+    assert_eq!(html, "<div class=\"a0\"></div>");
+}
+
+#[test]
+fn shorthands_come_before_longhands() {
+    let (css, html) = extract(div!([][
+        p!([][margin_top("4px"), margin("0")][]),
+        p!([][margin("0"), margin_top("4px")][])
+    ]));
+    assert_eq!(css, ".a0 {margin: 0;}.a1 {margin-top: 4px;}");
+    assert_eq!(html, "<div><p class=\"a0\"></p><p class=\"a0 a1\"></p></div>");
+}
+
+#[test]
+fn media_rules_are_ordered_by_breakpoint() {
+    let (css, html) = extract(div!([][
+        div!([][padding(responsive!(base : "0", lg : "3rem"))][]),
+        div!([][padding(responsive!(base : "0", md : "2rem", lg : "3rem"))][])
+    ]));
+    assert_eq!(
+        css,
+        ".a0 {padding: 0;}@media (min-width: 768px) {.a2 {padding: 2rem;}}@media (min-width: 1024px) {.a1 {padding: 3rem;}}"
+    );
+    assert_eq!(html, "<div><div class=\"a0 a1\"></div><div class=\"a0 a2 a1\"></div></div>");
+}
@@ -0,0 +1,55 @@
+// Checks that the critical CSS pass keeps the rules which could apply to a page, and defers the
+// rest.
+
+use dsl::*;
+use dsl::attr::{class, href};
+use dsl::css::critical_css;
+use dsl::nodes::*;
+
+fn page() -> Html {
+    html!([][
+        head!([][]),
+        body!([][
+            a!([class("button"), href("/")][text!["Home"]])
+        ])
+    ])
+}
+
+#[test]
+fn splits_selector_lists() {
+    let critical = critical_css(".button, .card { color: red; } .card { margin: 0; }", &page());
+    assert_eq!(critical.used, ".button {color: red;}");
+    assert_eq!(critical.unused, ".card {color: red;}.card {margin: 0;}");
+}
+
+#[test]
+fn keeps_selectors_which_state_could_match() {
+    let critical = critical_css("a:hover { color: red; } a:not(:hover) { color: blue; } p:hover { color: green; }", &page());
+    assert_eq!(critical.used, "a:hover {color: red;}a:not(:hover) {color: blue;}");
+    assert_eq!(critical.unused, "p:hover {color: green;}");
+}
+
+#[test]
+fn splits_grouping_rules() {
+    let css = "@media (min-width: 640px) { .button { padding: 0; } p { margin: 0; } } @keyframes spin { to { rotate: 1turn; } }";
+    let critical = critical_css(css, &page());
+    assert_eq!(critical.used, "@media (min-width: 640px) {.button {padding: 0;}}@keyframes spin {to { rotate: 1turn; }}");
+    assert_eq!(critical.unused, "@media (min-width: 640px) {p {margin: 0;}}");
+}
+
+#[test]
+fn skips_strings_and_comments() {
+    let css = "/* } */ a[href='{'] { content: \"}\"; } @import url(\"a;b.css\");";
+    let critical = critical_css(css, &page());
+    assert_eq!(critical.used, "@import url(\"a;b.css\");");
+    assert_eq!(critical.unused, "a[href='{'] {content: \"}\";}");
+}
+
+#[test]
+fn inserts_into_head() {
+    let mut page = page();
+    let critical = critical_css(".button { color: red; } p { margin: 0; }", &page);
+    assert!(critical.insert_into_head(&mut page, Some("/rest.css")));
+    let html = page.to_string();
+    assert!(html.contains("<head><style>.button {color: red;}</style><link rel=\"stylesheet\" href=\"/rest.css\" media=\"print\" onload=\"this.media='all'\">"));
+}
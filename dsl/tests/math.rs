@@ -0,0 +1,48 @@
+// Checks how lengths and percentages fold into the shortest value or `calc()` expression.
+
+use dsl::css::*;
+
+#[test]
+fn lengths_render_without_trailing_zeros() {
+    assert_eq!(px(4).to_string(), "4px");
+    assert_eq!(rem(1.5).to_string(), "1.5rem");
+    assert_eq!(pct(100.0 / 3.0).to_string(), "33.3333%");
+    assert_eq!((-px(2)).to_string(), "-2px");
+}
+
+#[test]
+fn same_units_are_added_together() {
+    assert_eq!((px(1) + px(2)).to_string(), "3px");
+    assert_eq!((rem(1) - rem(0.25)).to_string(), "0.75rem");
+    assert_eq!((pct(50) - pct(50)).to_string(), "0");
+}
+
+#[test]
+fn different_units_give_calc() {
+    assert_eq!((pct(100) - px(16)).to_string(), "calc(100% - 16px)");
+    assert_eq!((px(1) + rem(1) + px(1)).to_string(), "calc(2px + 1rem)");
+    assert_eq!(((vw(50) - px(8)) * 2.0).to_string(), "calc(100vw - 16px)");
+}
+
+#[test]
+fn terms_rounding_to_zero_are_dropped() {
+    assert_eq!((px(0.1) + px(0.2) - px(0.3) + rem(1)).to_string(), "1rem");
+    assert_eq!((px(0.1) + px(0.2) - px(0.3)).to_string(), "0");
+}
+
+#[test]
+fn math_functions() {
+    assert_eq!(Calc::min(vec![rem(1), vw(2)]).to_string(), "min(1rem, 2vw)");
+    assert_eq!(Calc::clamp(rem(1), vw(2) + rem(0.5), rem(2)).to_string(), "clamp(1rem, 2vw + 0.5rem, 2rem)");
+    assert_eq!((Calc::max(vec![Calc::from(px(10)), Calc::from(pct(5))]) * 2.0).to_string(), "calc(2 * max(10px, 5%))");
+    assert_eq!((Calc::max(vec![Calc::from(px(10)), Calc::from(pct(5))]) + px(4)).to_string(), "calc(max(10px, 5%) + 4px)");
+}
+
+#[test]
+fn non_finite_values_use_css_keywords() {
+    assert_eq!((px(1) / 0.0).to_string(), "calc(infinity * 1px)");
+    assert_eq!((-px(1) / 0.0).to_string(), "calc(-infinity * 1px)");
+    assert_eq!((pct(0) / 0.0).to_string(), "calc(NaN * 1%)");
+    assert_eq!((px(1) / 0.0 + rem(1)).to_string(), "calc(infinity * 1px + 1rem)");
+    assert_eq!((Calc::from(px(1)) / 0.0).to_string(), "calc(infinity * 1px)");
+}
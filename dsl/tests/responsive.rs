@@ -0,0 +1,72 @@
+// Checks that responsive values render their base value inline, add media variants in order of
+// breakpoint, and are written out as media query rules when rendered as a rule or hoisted.
+
+use dsl::*;
+use dsl::css::*;
+use dsl::nodes::*;
+
+#[test]
+fn base_value_renders_inline() {
+    let style = style![padding(responsive!(base : "0", md : "2rem"))];
+    assert_eq!(style.to_string(), "padding: 0;");
+}
+
+#[test]
+fn breakpoints_are_ordered_by_width() {
+    let style = style![
+        padding(responsive!(base : "0", lg : "3rem", sm : "1rem")),
+        margin(responsive!(base : "0", md : "2rem", sm : "1rem"))
+    ];
+    assert_eq!(
+        stylesheet![rule(".card", style)].to_string(),
+        ".card {padding: 0;margin: 0;}\
+        @media (min-width: 640px) {.card {padding: 1rem;margin: 1rem;}}\
+        @media (min-width: 768px) {.card {margin: 2rem;}}\
+        @media (min-width: 1024px) {.card {padding: 3rem;}}"
+    );
+}
+
+#[test]
+fn one_off_breakpoint() {
+    let value = Responsive::new("0").at(Breakpoint::new("50rem"), "1rem");
+    assert_eq!(stylesheet![rule("p", style![gap(value)])].to_string(), "p {gap: 0;}@media (min-width: 50rem) {p {gap: 1rem;}}");
+}
+
+#[test]
+fn hoists_responsive_styles() {
+    let mut root = div!([][
+        p!([][padding(responsive!(base : "0", md : "1rem"))][]),
+        p!([][padding(responsive!(base : "0", md : "1rem"))][])
+    ]);
+    let css = hoist_variants(&mut root).to_string();
+    let html = root.to_string();
+
+    let class = html.split('"').nth(1).unwrap().to_string();
+    assert_eq!(html, format!("<div><p class=\"{0}\"></p><p class=\"{0}\"></p></div>", class));
+    assert_eq!(css, format!(".{0} {{padding: 0;}}@media (min-width: 768px) {{.{0} {{padding: 1rem;}}}}", class));
+}
+
+// A project's own breakpoints, which replace the defaults wherever they are in scope.
+mod project {
+    use dsl::css::*;
+    use dsl::{responsive, style, stylesheet};
+
+    #[allow(non_upper_case_globals)]
+    mod breakpoints {
+        pub use dsl::css::breakpoints::*;
+        pub const md : dsl::css::Breakpoint = dsl::css::Breakpoint::new("50rem");
+        pub const tablet : dsl::css::Breakpoint = dsl::css::Breakpoint::new("40rem");
+    }
+
+    #[test]
+    fn project_breakpoints_override_defaults() {
+        let style = style![padding(responsive!(base : "0", sm : "1rem", tablet : "2rem", md : "3rem"))];
+        assert_eq!(
+            stylesheet![rule("p", style)].to_string(),
+            "p {padding: 0;}\
+            @media (min-width: 640px) {p {padding: 1rem;}}\
+            @media (min-width: 40rem) {p {padding: 2rem;}}\
+            @media (min-width: 50rem) {p {padding: 3rem;}}"
+        );
+    }
+}
@@ -0,0 +1,136 @@
+// Checks selector parsing and matching against a small page.
+
+use dsl::*;
+use dsl::attr::{class, href, id};
+use dsl::nodes::*;
+
+fn page() -> Html {
+    html!([][
+        head!([][]),
+        body!([][
+            nav!([id("top")][
+                ul!([][
+                    li!([class("active")][
+                        a!([href("/")][text!["Home"]])
+                    ]),
+                    li!([][
+                        a!([href("/about")][text!["About"]])
+                    ]),
+                    li!([class("last")][])
+                ])
+            ]),
+            p!([class("intro lead")][text!["Hello"]]),
+            p!([][])
+        ])
+    ])
+}
+
+fn hrefs(page : &Html, selector : &str) -> Vec<String> {
+    find_all(page, selector)
+    .unwrap()
+    .iter()
+    .map(|element| element.get_attribute("href").unwrap_or_default())
+    .collect()
+}
+
+fn count(page : &Html, selector : &str) -> usize {
+    find_all(page, selector).unwrap().len()
+}
+
+#[test]
+fn combinators() {
+    let page = page();
+    assert_eq!(hrefs(&page, "nav > ul li.active a"), ["/"]);
+    assert_eq!(hrefs(&page, "li + li > a"), ["/about"]);
+    assert_eq!(count(&page, "li ~ li"), 2);
+    assert_eq!(count(&page, "body > a"), 0);
+    assert_eq!(count(&page, "nav p"), 0);
+}
+
+#[test]
+fn compounds_and_attributes() {
+    let page = page();
+    assert_eq!(count(&page, "#top"), 1);
+    assert_eq!(count(&page, "p.intro.lead"), 1);
+    assert_eq!(count(&page, "P.INTRO"), 0);
+    assert_eq!(hrefs(&page, "a[href^='/a']"), ["/about"]);
+    assert_eq!(hrefs(&page, "a[href=\"/\"]"), ["/"]);
+    assert_eq!(count(&page, "[class~=lead]"), 1);
+    assert_eq!(count(&page, "a[href$=ABOUT i]"), 1);
+}
+
+#[test]
+fn structural_pseudo_classes() {
+    let page = page();
+    assert_eq!(count(&page, "li:first-child"), 1);
+    assert_eq!(count(&page, "li:nth-child(2n+1)"), 2);
+    assert_eq!(count(&page, "li:nth-last-child(1).last"), 1);
+    assert_eq!(count(&page, "p:last-of-type:empty"), 1);
+    assert_eq!(count(&page, "html:root"), 1);
+    assert_eq!(count(&page, "li:not(.active, .last)"), 1);
+    assert_eq!(count(&page, ":is(nav, p) a"), 2);
+    assert_eq!(count(&page, "li:has(a[href='/about'])"), 1);
+}
+
+#[test]
+fn invalid_selectors() {
+    assert_eq!(Selector::parse(""), Err(SelectorError::Empty));
+    assert_eq!(Selector::parse("a,"), Err(SelectorError::Empty));
+    assert_eq!(Selector::parse("a[href"), Err(SelectorError::Unterminated));
+    assert_eq!(Selector::parse(":nth-child(x)"), Err(SelectorError::InvalidArgument(String::from("x"))));
+    assert_eq!(Selector::parse("a:bogus"), Err(SelectorError::UnknownPseudoClass(String::from("bogus"))));
+    assert_eq!(Selector::parse("a > > b"), Err(SelectorError::Unexpected('>')));
+}
+
+#[test]
+fn relative_has() {
+    let page = page();
+    assert_eq!(count(&page, "li:has(> a)"), 2);
+    assert_eq!(count(&page, "ul:has(> a)"), 0);
+    assert_eq!(count(&page, "nav:has(li a)"), 1);
+    assert_eq!(count(&page, "li:has(+ .last)"), 1);
+    assert_eq!(count(&page, "li:has(~ li > a[href='/about'])"), 1);
+    assert_eq!(count(&page, "nav:has(+ p, > p)"), 1);
+    assert_eq!(count(&page, "body:has(> nav ul)"), 1);
+}
+
+#[test]
+fn state_never_matches_in_queries() {
+    let page = page();
+    assert_eq!(count(&page, "a:visited"), 0);
+    assert_eq!(count(&page, "a:hover, li.last"), 1);
+    assert_eq!(count(&page, "p::before"), 0);
+    assert_eq!(count(&page, "a:not(:hover)"), 2);
+    assert!(!Selector::parse("li:focus-within").unwrap().matches_any(&page));
+}
+
+fn form() -> Html {
+    html!([][
+        body!([][
+            form!([][
+                input!([attr::r#type("text")]),
+                input!([attr::r#type("checkbox"), attr::checked("")]),
+                input!([attr::readonly(""), attr::required("")]),
+                button!([attr::disabled("")][text!["Send"]]),
+                div!([attr::contenteditable("true")][])
+            ])
+        ])
+    ])
+}
+
+fn tags(page : &Html, selector : &str) -> Vec<&'static str> {
+    find_all(page, selector).unwrap().iter().map(|element| element.tag_name()).collect()
+}
+
+#[test]
+fn form_pseudo_classes() {
+    let form = form();
+    assert_eq!(tags(&form, ":enabled"), ["input", "input", "input"]);
+    assert_eq!(tags(&form, ":disabled"), ["button"]);
+    assert_eq!(tags(&form, ":checked"), ["input"]);
+    assert_eq!(count(&form, "input:required[readonly]"), 1);
+    assert_eq!(tags(&form, ":optional"), ["input", "input"]);
+    assert_eq!(tags(&form, ":read-write"), ["input", "div"]);
+    assert_eq!(tags(&form, ":read-only"), ["html", "body", "form", "input", "input", "button"]);
+    assert_eq!(tags(&page(), ":enabled"), Vec::<&str>::new());
+}
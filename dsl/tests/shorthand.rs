@@ -0,0 +1,73 @@
+// Checks that `Style::normalize` and `Style::merge` keep only the declarations which apply, and
+// that rendering a style collapses longhands into a shorthand only where that means the same.
+
+use dsl::css::*;
+use dsl::style;
+
+fn normalized(mut style : Style) -> String {
+    style.normalize();
+    style.to_string()
+}
+
+#[test]
+fn later_longhand_overrides_part_of_shorthand() {
+    assert_eq!(normalized(style![margin("0"), margin_top("4px")]), "margin: 4px 0 0;");
+}
+
+#[test]
+fn later_shorthand_overrides_longhand() {
+    assert_eq!(normalized(style![margin_top("4px"), margin("0 8px")]), "margin: 0 8px;");
+}
+
+#[test]
+fn important_longhand_survives_later_shorthand() {
+    assert_eq!(
+        normalized(style![padding_left("1px !important"), padding("2px")]),
+        "padding-left: 1px !important;padding-top: 2px;padding-right: 2px;padding-bottom: 2px;"
+    );
+}
+
+#[test]
+fn shorthand_with_var_is_kept() {
+    assert_eq!(normalized(style![margin("var(--space)"), margin_top("0")]), "margin: var(--space);margin-top: 0;");
+}
+
+#[test]
+fn multi_word_alignment_is_not_split() {
+    assert_eq!(normalized(style![place_items("safe center")]), "place-items: safe center;");
+    assert_eq!(normalized(style![place_items("first baseline")]), "place-items: first baseline;");
+    assert_eq!(normalized(style![place_items("center start")]), "place-items: center start;");
+}
+
+#[test]
+fn elliptical_radii_are_not_collapsed() {
+    let style = style![
+        border_top_left_radius("4px 8px"),
+        border_top_right_radius("4px 8px"),
+        border_bottom_right_radius("4px 8px"),
+        border_bottom_left_radius("4px 8px")
+    ];
+    assert_eq!(
+        style.to_string(),
+        "border-top-left-radius: 4px 8px;border-top-right-radius: 4px 8px;border-bottom-right-radius: 4px 8px;border-bottom-left-radius: 4px 8px;"
+    );
+}
+
+#[test]
+fn complete_longhands_collapse() {
+    let style = style![padding_top("1px"), padding_right("2px"), padding_bottom("1px"), padding_left("2px")];
+    assert_eq!(style.to_string(), "padding: 1px 2px;");
+}
+
+#[test]
+fn mixed_importance_is_not_collapsed() {
+    let style = style![row_gap("1px !important"), column_gap("1px")];
+    assert_eq!(style.to_string(), "row-gap: 1px !important;column-gap: 1px;");
+}
+
+#[test]
+fn merge_overrides_with_other_style() {
+    let mut base = style![margin("0"), color("black")];
+    base.merge(style![margin_left("auto"), color("red")]);
+    assert_eq!(base.to_string(), "margin: 0 0 0 auto;color: red;");
+}
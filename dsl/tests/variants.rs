@@ -0,0 +1,60 @@
+// Checks that variants render as nested rules, and that hoisting moves styles with variants into a
+// stylesheet without mixing up different styles.
+
+use dsl::*;
+use dsl::css::*;
+use dsl::nodes::*;
+
+#[test]
+fn variants_render_in_rules() {
+    let style = style![
+        color("black"),
+        hover(style![color("blue")]),
+        before(style![content("'>'")]),
+        pseudo(":nth-child(2n)", style![color("gray")]),
+        media_query("(max-width: 600px)", style![padding("0")]),
+        dark(style![color("white")])
+    ];
+    assert_eq!(
+        stylesheet![rule(".link", style)].to_string(),
+        ".link {color: black;}\
+        .link:hover {color: blue;}\
+        .link::before {content: '>';}\
+        .link:nth-child(2n) {color: gray;}\
+        @media (max-width: 600px) {.link {padding: 0;}}\
+        @media (prefers-color-scheme: dark) {.link {color: white;}}"
+    );
+}
+
+#[test]
+fn variants_apply_to_each_selector_of_a_list() {
+    let style = style![hover(style![color("blue")])];
+    assert_eq!(stylesheet![rule("a, button", style)].to_string(), "a:hover, button:hover {color: blue;}");
+}
+
+fn classes(html : &str) -> Vec<String> {
+    html.split("class=\"").skip(1).map(|rest| rest.split('"').next().unwrap().to_string()).collect()
+}
+
+#[test]
+fn hoisting_shares_classes_between_identical_styles() {
+    let mut root = div!([][
+        a!([][color("black"), hover(style![color("blue")])][]),
+        a!([][color("black"), hover(style![color("blue")])][]),
+        a!([][color("black"), hover(style![color("red")])][]),
+        p!([][color("black")][])
+    ]);
+    let css = hoist_variants(&mut root).to_string();
+    let html = root.to_string();
+
+    let classes = classes(&html);
+    assert_eq!(classes.len(), 3);
+    assert_eq!(classes[0], classes[1]);
+    assert_ne!(classes[0], classes[2]);
+    assert_eq!(
+        css,
+        format!(".{0} {{color: black;}}.{0}:hover {{color: blue;}}.{1} {{color: black;}}.{1}:hover {{color: red;}}", classes[0], classes[2])
+    );
+    // Styles without variants stay inline.
+    assert!(html.ends_with("<p style=\"color: black;\"></p></div>"));
+}
@@ -2,6 +2,8 @@ extern crate proc_macro;
 use proc_macro::TokenStream;
 use proc_macro2::Ident;
 use quote::{quote, format_ident};
+use syn::{parse_macro_input, LitStr, Token};
+use syn::parse::{Parse, ParseStream};
 
 fn split_args(token_stream : TokenStream) -> (Ident, Ident, proc_macro2::TokenStream) {
     let args : Vec<String> =
@@ -26,7 +28,45 @@ pub fn parent_node(input : TokenStream) -> TokenStream {
             css_props : std::option::Option<Style>,
         }
 
-        impl Node for #rust_name {}
+        impl Node for #rust_name {
+            fn as_element(&self) -> std::option::Option<&dyn Element> {
+                Some(self)
+            }
+
+            fn as_element_mut(&mut self) -> std::option::Option<&mut dyn Element> {
+                Some(self)
+            }
+        }
+
+        impl Element for #rust_name {
+            fn tag_name(&self) -> &'static str {
+                stringify!(#html_name)
+            }
+
+            fn attributes(&self) -> &[Box<dyn Attribute>] {
+                &self.attributes
+            }
+
+            fn attributes_mut(&mut self) -> &mut Vec<Box<dyn Attribute>> {
+                &mut self.attributes
+            }
+
+            fn css_props(&self) -> std::option::Option<&Style> {
+                self.css_props.as_ref()
+            }
+
+            fn css_props_mut(&mut self) -> &mut std::option::Option<Style> {
+                &mut self.css_props
+            }
+
+            fn children(&self) -> std::option::Option<&[Box<dyn Node>]> {
+                Some(&self.children)
+            }
+
+            fn children_mut(&mut self) -> std::option::Option<&mut Vec<Box<dyn Node>>> {
+                Some(&mut self.children)
+            }
+        }
 
         impl ToString for #rust_name {
             fn to_string(&self) -> String {
@@ -38,9 +78,7 @@ pub fn parent_node(input : TokenStream) -> TokenStream {
 
                 if let Some(props) = &self.css_props {
                     formatted.push_str(" style=\"");
-                    for prop in &props.0 {
-                        formatted.push_str(&prop.to_string());
-                    }
+                    formatted.push_str(&props.to_string());
                     formatted.push_str("\"");
                 }
                 formatted.push_str(">");
@@ -137,7 +175,45 @@ pub fn void_node(input : TokenStream) -> TokenStream {
             css_props : std::option::Option<Style>,
         }
 
-        impl Node for #rust_name {}
+        impl Node for #rust_name {
+            fn as_element(&self) -> std::option::Option<&dyn Element> {
+                Some(self)
+            }
+
+            fn as_element_mut(&mut self) -> std::option::Option<&mut dyn Element> {
+                Some(self)
+            }
+        }
+
+        impl Element for #rust_name {
+            fn tag_name(&self) -> &'static str {
+                stringify!(#html_name)
+            }
+
+            fn attributes(&self) -> &[Box<dyn Attribute>] {
+                &self.attributes
+            }
+
+            fn attributes_mut(&mut self) -> &mut Vec<Box<dyn Attribute>> {
+                &mut self.attributes
+            }
+
+            fn css_props(&self) -> std::option::Option<&Style> {
+                self.css_props.as_ref()
+            }
+
+            fn css_props_mut(&mut self) -> &mut std::option::Option<Style> {
+                &mut self.css_props
+            }
+
+            fn children(&self) -> std::option::Option<&[Box<dyn Node>]> {
+                None
+            }
+
+            fn children_mut(&mut self) -> std::option::Option<&mut Vec<Box<dyn Node>>> {
+                None
+            }
+        }
 
         impl ToString for #rust_name {
             fn to_string(&self) -> String {
@@ -148,9 +224,7 @@ pub fn void_node(input : TokenStream) -> TokenStream {
                 }
                 if let Some(props) = &self.css_props {
                     formatted.push_str(" style=\"");
-                    for prop in &props.0 {
-                        formatted.push_str(&prop.to_string());
-                    }
+                    formatted.push_str(&props.to_string());
                     formatted.push_str("\"");
                 }
                 formatted.push_str(">");
@@ -224,11 +298,19 @@ pub fn attribute(input : TokenStream) -> TokenStream {
             value : String,
         }
 
-        impl Attribute for #rust_name {}
+        impl Attribute for #rust_name {
+            fn name(&self) -> String {
+                remove_whitespace(stringify!(#html_name))
+            }
+
+            fn value(&self) -> String {
+                self.value.clone()
+            }
+        }
 
         impl ToString for #rust_name {
             fn to_string(&self) -> String {
-                format!("{}=\"{}\"", remove_whitespace(stringify!(#html_name)), self.value)
+                format!("{}=\"{}\"", self.name(), self.value)
             }
         }
 
@@ -249,19 +331,33 @@ pub fn css_prop(input : TokenStream) -> TokenStream {
     let code = quote! {
         pub struct #rust_name {
             value : String,
+            responsive : Vec<(String, String)>,
         }
 
-        impl CssProp for #rust_name {}
+        impl CssProp for #rust_name {
+            fn name(&self) -> String {
+                remove_whitespace(stringify!(#html_name))
+            }
+
+            fn value(&self) -> String {
+                self.value.clone()
+            }
+
+            fn responsive(&self) -> Vec<(String, String)> {
+                self.responsive.clone()
+            }
+        }
 
         impl ToString for #rust_name {
             fn to_string(&self) -> String {
-                format!("{}: {};", remove_whitespace(stringify!(#html_name)), self.value)
+                format!("{}: {};", self.name(), self.value)
             }
         }
 
-        pub fn #function_name(value : &str) -> #rust_name {
+        pub fn #function_name<V : CssValue>(value : V) -> #rust_name {
             #rust_name {
-                value : String::from(value)
+                value : value.to_string(),
+                responsive : value.responsive(),
             }
         }
     };
@@ -269,132 +365,195 @@ pub fn css_prop(input : TokenStream) -> TokenStream {
     code.into()
 }
 
-#[proc_macro]
-pub fn all_nodes(_ : TokenStream) -> TokenStream {
+// Vendored spec data that the `all_*` tables are generated from.
+const HTML_ELEMENTS : &str = include_str!("../spec/html-elements.txt");
+const HTML_ATTRIBUTES : &str = include_str!("../spec/html-attributes.txt");
+const CSS_PROPERTIES : &str = include_str!("../spec/css-properties.txt");
+
+// Rust names for spec names which are several words run together, or which would collide with
+// other items in the generated module.
+const RUST_NAMES : &[(&str, &str)] = &[
+    ("blockquote", "BlockQuote"),
+    ("colgroup", "ColGroup"),
+    ("datalist", "DataList"),
+    ("fieldset", "FieldSet"),
+    ("figcaption", "FigCaption"),
+    ("hgroup", "HGroup"),
+    ("iframe", "IFrame"),
+    ("noscript", "NoScript"),
+    ("optgroup", "OptGroup"),
+    ("samp", "SAmp"),
+    ("tbody", "TBody"),
+    ("textarea", "TextArea"),
+    ("tfoot", "TFoot"),
+    ("thead", "THead"),
+    ("accesskey", "AccessKey"),
+    ("allowfullscreen", "AllowFullscreen"),
+    ("autocapitalize", "AutoCapitalize"),
+    ("autocomplete", "AutoComplete"),
+    ("autocorrect", "AutoCorrect"),
+    ("autofocus", "AutoFocus"),
+    ("autoplay", "AutoPlay"),
+    ("charset", "CharSet"),
+    ("colspan", "ColSpan"),
+    ("contenteditable", "ContentEditable"),
+    ("crossorigin", "CrossOrigin"),
+    ("datetime", "DateTime"),
+    ("dirname", "DirName"),
+    ("enctype", "EncType"),
+    ("enterkeyhint", "EnterKeyHint"),
+    ("fetchpriority", "FetchPriority"),
+    ("formaction", "FormAction"),
+    ("formenctype", "FormEncType"),
+    ("formmethod", "FormMethod"),
+    ("formnovalidate", "FormNoValidate"),
+    ("formtarget", "FormTarget"),
+    ("hreflang", "HrefLang"),
+    ("imagesizes", "ImageSizes"),
+    ("imagesrcset", "ImageSrcSet"),
+    ("inputmode", "InputMode"),
+    ("ismap", "IsMap"),
+    ("itemid", "ItemId"),
+    ("itemprop", "ItemProp"),
+    ("itemref", "ItemRef"),
+    ("itemscope", "ItemScope"),
+    ("itemtype", "ItemType"),
+    ("maxlength", "MaxLength"),
+    ("minlength", "MinLength"),
+    ("nomodule", "NoModule"),
+    ("novalidate", "NoValidate"),
+    ("placeholder", "PlaceHolder"),
+    ("playsinline", "PlaysInline"),
+    ("popovertarget", "PopoverTarget"),
+    ("popovertargetaction", "PopoverTargetAction"),
+    ("referrerpolicy", "ReferrerPolicy"),
+    ("rowspan", "RowSpan"),
+    ("shadowrootclonable", "ShadowRootClonable"),
+    ("shadowrootdelegatesfocus", "ShadowRootDelegatesFocus"),
+    ("shadowrootmode", "ShadowRootMode"),
+    ("shadowrootserializable", "ShadowRootSerializable"),
+    ("spellcheck", "SpellCheck"),
+    ("srcdoc", "SrcDoc"),
+    ("srclang", "SrcLang"),
+    ("srcset", "SrcSet"),
+    ("tabindex", "TabIndex"),
+    ("usemap", "UseMap"),
+    ("writingsuggestions", "WritingSuggestions"),
+    ("onafterprint", "OnAfterPrint"),
+    ("onauxclick", "OnAuxClick"),
+    ("onbeforeinput", "OnBeforeInput"),
+    ("onbeforematch", "OnBeforeMatch"),
+    ("onbeforeprint", "OnBeforePrint"),
+    ("onbeforetoggle", "OnBeforeToggle"),
+    ("onbeforeunload", "OnBeforeUnload"),
+    ("oncanplay", "OnCanPlay"),
+    ("oncanplaythrough", "OnCanPlaythrough"),
+    ("oncontextlost", "OnContextLost"),
+    ("oncontextmenu", "OnContextMenu"),
+    ("oncontextrestored", "OnContextRestored"),
+    ("oncuechange", "OnCueChange"),
+    ("ondblclick", "OndblClick"),
+    ("ondragend", "OnDragEnd"),
+    ("ondragenter", "OnDragEnter"),
+    ("ondragleave", "OnDragLeave"),
+    ("ondragover", "OnDragOver"),
+    ("ondragstart", "OnDragStart"),
+    ("ondurationchange", "OndurationChange"),
+    ("onformdata", "OnFormData"),
+    ("onhashchange", "OnHashChange"),
+    ("onlanguagechange", "OnLanguageChange"),
+    ("onloadeddata", "OnLoadedData"),
+    ("onloadedmetadata", "OnLoadedMetadata"),
+    ("onloadstart", "OnLoadStart"),
+    ("onmessageerror", "OnMessageError"),
+    ("onmousedown", "OnMouseDown"),
+    ("onmouseenter", "OnMouseEnter"),
+    ("onmouseleave", "OnMouseLeave"),
+    ("onmousemove", "OnMouseMove"),
+    ("onmouseout", "OnMouseOut"),
+    ("onmouseover", "OnMouseOver"),
+    ("onmouseup", "OnMouseUp"),
+    ("onpagehide", "OnPageHide"),
+    ("onpageshow", "OnPageShow"),
+    ("onpopstate", "OnPopState"),
+    ("onratechange", "OnRateChange"),
+    ("onrejectionhandled", "OnRejectionHandled"),
+    ("onscrollend", "OnScrollEnd"),
+    ("onsecuritypolicyviolation", "OnSecurityPolicyViolation"),
+    ("onslotchange", "OnSlotChange"),
+    ("ontimeupdate", "OnTimeUpdate"),
+    ("onunhandledrejection", "OnUnhandledRejection"),
+    ("onvolumechange", "OnVolumeChange"),
+];
+
+// Rust and macro names for elements which would collide with other items in the generated
+// modules. These only apply to elements, as the `style` attribute keeps its own name.
+const ELEMENT_NAMES : &[(&str, &str, &str)] = &[
+    ("style", "StyleElement", "style_element"),
+];
+
+// Rust and function names for attributes which would make a CSS property ambiguous when `attr::*`
+// and `css::*` are both glob imported. These only apply to attributes, as the property keeps its
+// own name.
+const ATTRIBUTE_NAMES : &[(&str, &str, &str)] = &[
+    ("translate", "TranslateAttribute", "translate_attribute"),
+];
+
+const KEYWORDS : &[&str] = &["as", "async", "for", "loop", "type"];
+
+// Reads the non-comment lines of a vendored spec file, split into whitespace separated fields.
+fn spec_entries(data : &str) -> Vec<Vec<&str>> {
+    data
+    .lines()
+    .map(|line| line.trim())
+    .filter(|line| !line.is_empty() && !line.starts_with('#'))
+    .map(|line| line.split_whitespace().collect())
+    .collect()
+}
 
-    let parent_idents = vec![
-        ("A", "a", "a"),
-        ("Abbr", "abbr", "abbr"),
-        ("Address", "address", "address"),
-        ("Article", "article", "article"),
-        ("Aside", "aside", "aside"),
-        ("Audio", "audio", "audio"),
-        ("B", "b", "b"),
-        ("Bdi", "bdi", "bdi"),
-        ("Bdo", "bdo", "bdo"),
-        ("BlockQuote", "blockquote", "blockquote"),
-        ("Body", "body", "body"),
-        ("Button", "button", "button"),
-        ("Canvas", "canvas", "canvas"),
-        ("Caption", "caption", "caption"),
-        ("Cite", "cite", "cite"),
-        ("Code", "code", "code"),
-        ("ColGroup", "colgroup", "colgroup"),
-        ("Data", "data", "data"),
-        ("DataList", "datalist", "datalist"),
-        ("Dd", "dd", "dd"),
-        ("Del", "del", "del"),
-        ("Details", "details", "details"),
-        ("Dfn", "dfn", "dfn"),
-        ("Dialog", "dialog", "dialog"),
-        ("Div", "div", "div"),
-        ("Dl", "dl", "dl"),
-        ("Dt", "dt", "dt"),
-        ("Em", "em", "em"),
-        ("FieldSet", "fieldset", "fieldset"),
-        ("FigCaption", "figcaption", "figcaption"),
-        ("Figure", "figure", "figure"),
-        ("Footer", "footer", "footer"),
-        ("Form", "form", "form"),
-        ("H1", "h1", "h1"),
-        ("H2", "h2", "h2"),
-        ("H3", "h3", "h3"),
-        ("H4", "h4", "h4"),
-        ("H5", "h5", "h5"),
-        ("H6", "h6", "h6"),
-        ("Head", "head", "head"),
-        ("Header", "header", "header"),
-        ("Html", "html", "html"),
-        ("I", "i", "i"),
-        ("IFrame", "iframe", "iframe"),
-        ("Ins", "ins", "ins"),
-        ("Kbd", "kbd", "kbd"),
-        ("Label", "label", "label"),
-        ("Legend", "legend", "legend"),
-        ("Li", "li", "li"),
-        ("Main", "main", "main"),
-        ("Map", "map", "map"),
-        ("Mark", "mark", "mark"),
-        ("Meter", "meter", "meter"),
-        ("Nav", "nav", "nav"),
-        ("NoScript", "noscript", "noscript"),
-        ("Object", "object", "object"),
-        ("Ol", "ol", "ol"),
-        ("OptGroup", "optgroup", "optgroup"),
-        ("Option", "option", "option"),
-        ("Output", "output", "output"),
-        ("P", "p", "p"),
-        ("Picture", "picture", "picture"),
-        ("Pre", "pre", "pre"),
-        ("Progress", "progress", "progress"),
-        ("Q", "q", "q"),
-        ("Rp", "rp", "rp"),
-        ("Rt", "rt", "rt"),
-        ("Ruby", "ruby", "ruby"),
-        ("S", "s", "s"),
-        ("SAmp", "samp", "samp"),
-        ("Script", "script", "script"),
-        ("Section", "section", "section"),
-        ("Select", "select", "select"),
-        ("Small", "small", "small"),
-        ("Span", "span", "span"),
-        ("Strong", "strong", "strong"),
-        ("Sub", "sub", "sub"),
-        ("Summary", "summary", "summary"),
-        ("Sup", "sup", "sup"),
-        ("Svg", "svg", "svg"),
-        ("Table", "table", "table"),
-        ("TBody", "tbody", "tbody"),
-        ("Td", "td", "td"),
-        ("Template", "template", "template"),
-        ("TextArea", "textarea", "textarea"),
-        ("TFoot", "tfoot", "tfoot"),
-        ("Th", "th", "th"),
-        ("THead", "thead", "thead"),
-        ("Time", "time", "time"),
-        ("Title", "title", "title"),
-        ("Tr", "tr", "tr"),
-        ("U", "u", "u"),
-        ("Ul", "ul", "ul"),
-        ("Var", "var", "var"),
-        ("Video", "video", "video"),
-    ];
-
-    let void_idents = vec![
-        ("Area", "area", "area"),
-        ("Base", "base", "base"),
-        ("Br", "br", "br"),
-        ("Col", "col", "col"),
-        ("Embed", "embed", "embed"),
-        ("Hr", "hr", "hr"),
-        ("Img", "img", "img"),
-        ("Input", "input", "input"),
-        ("Link", "link", "link"),
-        ("Meta", "meta", "meta"),
-        ("Param", "param", "param"),
-        ("Source", "source", "source"),
-        ("Track", "track", "track"),
-        ("Wbr", "wbr", "wbr"),
-    ];
+// `accept-charset` becomes `AcceptCharset`, and `ontoggle` becomes `OnToggle`.
+fn rust_name(spec_name : &str) -> String {
+    if let Some((_, name)) = RUST_NAMES.iter().find(|(spec, _)| *spec == spec_name) {
+        return name.to_string();
+    }
 
-    let mut code = String::new();
+    let capitalise = |word : &str| {
+        let mut chars = word.chars();
+        match chars.next() {
+            Some(first) => first.to_uppercase().chain(chars).collect(),
+            None => String::new(),
+        }
+    };
 
-    for (rust_name, macro_name, html_name) in parent_idents {
-        code.push_str(&format!("parent_node!({}, {}, {});", rust_name, macro_name, html_name));
+    match spec_name.strip_prefix("on") {
+        Some(event) if spec_name != "on" => format!("On{}", capitalise(event)),
+        _ => spec_name.split('-').map(capitalise).collect(),
     }
+}
 
-    for (rust_name, macro_name, html_name) in void_idents {
-        code.push_str(&format!("void_node!({}, {}, {});", rust_name, macro_name, html_name));
+// `accept-charset` becomes `accept_charset`, and keywords such as `for` become `r#for`.
+fn function_name(spec_name : &str) -> String {
+    let name = spec_name.replace('-', "_");
+    if KEYWORDS.contains(&name.as_str()) {
+        format!("r#{}", name)
+    }
+    else {
+        name
+    }
+}
+
+#[proc_macro]
+pub fn all_nodes(_ : TokenStream) -> TokenStream {
+    let mut code = String::new();
+
+    for entry in spec_entries(HTML_ELEMENTS) {
+        let (html_name, kind) = (entry[0], entry[1]);
+        let generator = if kind == "void" { "void_node" } else { "parent_node" };
+        let (rust_name, macro_name) = match ELEMENT_NAMES.iter().find(|(spec, _, _)| *spec == html_name) {
+            Some((_, rust_name, macro_name)) => (rust_name.to_string(), macro_name.to_string()),
+            None => (rust_name(html_name), function_name(html_name)),
+        };
+        code.push_str(&format!("{}!({}, {}, {});", generator, rust_name, macro_name, html_name));
     }
 
     code.parse().unwrap()
@@ -402,175 +561,14 @@ pub fn all_nodes(_ : TokenStream) -> TokenStream {
 
 #[proc_macro]
 pub fn all_attributes(_ : TokenStream) -> TokenStream {
-
-    let idents = vec![
-        ("Accept", "accept", "accept"),
-        ("AcceptCharset", "accept_charset", "accept-charset"),
-        ("AccessKey", "accesskey", "accesskey"),
-        ("Action", "action", "action"),
-        ("Alt", "alt", "alt"),
-        ("Async", "r#async", "async"),
-        ("AutoComplete", "autocomplete", "autocomplete"),
-        ("AutoFocus", "autofocus", "autofocus"),
-        ("AutoPlay", "autoplay", "autoplay"),
-        ("CharSet", "charset", "charset"),
-        ("Checked", "checked", "checked"),
-        ("Cite", "cite", "cite"),
-        ("Class", "class", "class"),
-        ("Cols", "cols", "cols"),
-        ("ColSpan", "colspan", "colspan"),
-        ("Content", "content", "content"),
-        ("ContentEditable", "contenteditable", "contenteditable"),
-        ("Controls", "controls", "controls"),
-        ("Coords", "coords", "coords"),
-        ("Data", "data", "data"),
-        ("DateTime", "datetime", "datetime"),
-        ("Default", "default", "default"),
-        ("Defer", "defer", "defer"),
-        ("Dir", "dir", "dir"),
-        ("DirName", "dirname", "dirname"),
-        ("Disabled", "disabled", "disabled"),
-        ("Download", "download", "download"),
-        ("Draggable", "draggable", "draggable"),
-        ("EncType", "enctype", "enctype"),
-        ("For", "r#for", "for"),
-        ("Form", "form", "form"),
-        ("FormAction", "formaction", "formaction"),
-        ("Headers", "headers", "headers"),
-        ("Height", "height", "height"),
-        ("Hidden", "hidden", "hidden"),
-        ("High", "high", "high"),
-        ("Href", "href", "href"),
-        ("HrefLang", "hreflang", "hreflang"),
-        ("HttpEquiv", "http_equiv", "http-equiv"),
-        ("Id", "id", "id"),
-        ("IsMap", "ismap", "ismap"),
-        ("Kind", "kind", "kind"),
-        ("Label", "label", "label"),
-        ("Lang", "lang", "lang"),
-        ("List", "list", "list"),
-        ("Loop", "r#loop", "loop"),
-        ("Low", "low", "low"),
-        ("Max", "max", "max"),
-        ("MaxLength", "maxlength", "maxlength"),
-        ("Media", "media", "media"),
-        ("Method", "method", "method"),
-        ("Min", "min", "min"),
-        ("Multiple", "multiple", "multiple"),
-        ("Muted", "muted", "muted"),
-        ("Name", "name", "name"),
-        ("NoValidate", "novalidate", "novalidate"),
-        ("OnAbort", "onabort", "onabort"),
-        ("OnAfterPrint", "onafterprint", "onafterprint"),
-        ("OnBeforePrint", "onbeforeprint", "onbeforeprint"),
-        ("OnBeforeUnload", "onbeforeunload", "onbeforeunload"),
-        ("OnBlur", "onblur", "onblur"),
-        ("OnCanPlay", "oncanplay", "oncanplay"),
-        ("OnCanPlaythrough", "oncanplaythrough", "oncanplaythrough"),
-        ("OnChange", "onchange", "onchange"),
-        ("OnClick", "onclick", "onclick"),
-        ("OnContextMenu", "oncontextmenu", "oncontextmenu"),
-        ("OnCopy", "oncopy", "oncopy"),
-        ("OnCueChange", "oncuechange", "oncuechange"),
-        ("OnCut", "oncut", "oncut"),
-        ("OndblClick", "ondblclick", "ondblclick"),
-        ("OnDrag", "ondrag", "ondrag"),
-        ("OnDragEnd", "ondragend", "ondragend"),
-        ("OnDragEnter", "ondragenter", "ondragenter"),
-        ("OnDragLeave", "ondragleave", "ondragleave"),
-        ("OnDragOver", "ondragover", "ondragover"),
-        ("OnDragStart", "ondragstart", "ondragstart"),
-        ("OnDrop", "ondrop", "ondrop"),
-        ("OndurationChange", "ondurationchange", "ondurationchange"),
-        ("OnEmptied", "onemptied", "onemptied"),
-        ("OnEnded", "onended", "onended"),
-        ("OnError", "onerror", "onerror"),
-        ("OnFocus", "onfocus", "onfocus"),
-        ("OnHashChange", "onhashchange", "onhashchange"),
-        ("OnInput", "oninput", "oninput"),
-        ("OnInvalid", "oninvalid", "oninvalid"),
-        ("OnKeydown", "onkeydown", "onkeydown"),
-        ("OnKeypress", "onkeypress", "onkeypress"),
-        ("OnKeyup", "onkeyup", "onkeyup"),
-        ("OnLoad", "onload", "onload"),
-        ("OnLoadedData", "onloadeddata", "onloadeddata"),
-        ("OnLoadedMetadata", "onloadedmetadata", "onloadedmetadata"),
-        ("OnLoadStart", "onloadstart", "onloadstart"),
-        ("OnMouseDown", "onmousedown", "onmousedown"),
-        ("OnMouseMove", "onmousemove", "onmousemove"),
-        ("OnMouseOut", "onmouseout", "onmouseout"),
-        ("OnMouseOver", "onmouseover", "onmouseover"),
-        ("OnMouseUp", "onmouseup", "onmouseup"),
-        ("OnMouseWheel", "onmousewheel", "onmousewheel"),
-        ("OnOffline", "onoffline", "onoffline"),
-        ("OnOnline", "ononline", "ononline"),
-        ("OnPageHide", "onpagehide", "onpagehide"),
-        ("OnPageShow", "onpageshow", "onpageshow"),
-        ("OnPaste", "onpaste", "onpaste"),
-        ("OnPause", "onpause", "onpause"),
-        ("OnPlay", "onplay", "onplay"),
-        ("OnPlaying", "onplaying", "onplaying"),
-        ("OnPopState", "onpopstate", "onpopstate"),
-        ("OnProgress", "onprogress", "onprogress"),
-        ("OnRateChange", "onratechange", "onratechange"),
-        ("OnReset", "onreset", "onreset"),
-        ("OnResize", "onresize", "onresize"),
-        ("OnScroll", "onscroll", "onscroll"),
-        ("OnSearch", "onsearch", "onsearch"),
-        ("OnSeeked", "onseeked", "onseeked"),
-        ("OnSeeking", "onseeking", "onseeking"),
-        ("OnSelect", "onselect", "onselect"),
-        ("OnStalled", "onstalled", "onstalled"),
-        ("OnStorage", "onstorage", "onstorage"),
-        ("OnSubmit", "onsubmit", "onsubmit"),
-        ("OnSuspend", "onsuspend", "onsuspend"),
-        ("OnTimeUpdate", "ontimeupdate", "ontimeupdate"),
-        ("OnToggle", "ontoggle", "ontoggle"),
-        ("OnUnload", "onunload", "onunload"),
-        ("OnVolumeChange", "onvolumechange", "onvolumechange"),
-        ("OnWaiting", "onwaiting", "onwaiting"),
-        ("OnWheel", "onwheel", "onwheel"),
-        ("Open", "open", "open"),
-        ("Optimum", "optimum", "optimum"),
-        ("Pattern", "pattern", "pattern"),
-        ("PlaceHolder", "placeholder", "placeholder"),
-        ("Poster", "poster", "poster"),
-        ("Preload", "preload", "preload"),
-        ("Readonly", "readonly", "readonly"),
-        ("Rel", "rel", "rel"),
-        ("Required", "required", "required"),
-        ("Reversed", "reversed", "reversed"),
-        ("Rows", "rows", "rows"),
-        ("RowSpan", "rowspan", "rowspan"),
-        ("Sandbox", "sandbox", "sandbox"),
-        ("Scope", "scope", "scope"),
-        ("Selected", "selected", "selected"),
-        ("Shape", "shape", "shape"),
-        ("Size", "size", "size"),
-        ("Sizes", "sizes", "sizes"),
-        ("Span", "span", "span"),
-        ("SpellCheck", "spellcheck", "spellcheck"),
-        ("Src", "src", "src"),
-        ("SrcDoc", "srcdoc", "srcdoc"),
-        ("SrcLang", "srclang", "srclang"),
-        ("SrcSet", "srcset", "srcset"),
-        ("Start", "start", "start"),
-        ("Step", "step", "step"),
-        ("Style", "style", "style"),
-        ("TabIndex", "tabindex", "tabindex"),
-        ("Target", "target", "target"),
-        ("Title", "title", "title"),
-        ("Translate", "translate", "translate"),
-        ("Type", "r#type", "type"),
-        ("UseMap", "usemap", "usemap"),
-        ("Value", "value", "value"),
-        ("Width", "width", "width"),
-        ("Wrap", "wrap", "wrap"),
-    ];
-
     let mut code = String::new();
 
-    for (rust_name, function_name, html_name) in idents {
+    for entry in spec_entries(HTML_ATTRIBUTES) {
+        let html_name = entry[0];
+        let (rust_name, function_name) = match ATTRIBUTE_NAMES.iter().find(|(spec, _, _)| *spec == html_name) {
+            Some((_, rust_name, function_name)) => (rust_name.to_string(), function_name.to_string()),
+            None => (rust_name(html_name), function_name(html_name)),
+        };
         code.push_str(&format!("attribute!({}, {}, {});", rust_name, function_name, html_name));
     }
 
@@ -579,213 +577,299 @@ pub fn all_attributes(_ : TokenStream) -> TokenStream {
 
 #[proc_macro]
 pub fn all_css_props(_ : TokenStream) -> TokenStream {
-
-    let idents = vec![
-        ("AlignContent", "align_content", "align-content"),
-        ("AlignItems", "align_items", "align-items"),
-        ("AlignSelf", "align_self", "align-self"),
-        ("All", "all", "all"),
-        ("Animation", "animation", "animation"),
-        ("AnimationDelay", "animation_delay", "animation-delay"),
-        ("AnimationDirection", "animation_direction", "animation-direction"),
-        ("AnimationDuration", "animation_duration", "animation-duration"),
-        ("AnimationFillMode", "animation_fill_mode", "animation-fill-mode"),
-        ("AnimationIterationCount", "animation_iteration_count", "animation-iteration-count"),
-        ("AnimationName", "animation_name", "animation-name"),
-        ("AnimationPlayState", "animation_play_state", "animation-play-state"),
-        ("AnimationTimingFunction", "animation_timing_function", "animation-timing-function"),
-        ("BackfaceVisibility", "backface_visibility", "backface-visibility"),
-        ("Background", "background", "background"),
-        ("BackgroundAttachment", "background_attachment", "background-attachment"),
-        ("BackgroundBlendMode", "background_blend_mode", "background-blend-mode"),
-        ("BackgroundClip", "background_clip", "background-clip"),
-        ("BackgroundColor", "background_color", "background-color"),
-        ("BackgroundImage", "background_image", "background-image"),
-        ("BackgroundOrigin", "background_origin", "background-origin"),
-        ("BackgroundPosition", "background_position", "background-position"),
-        ("BackgroundRepeat", "background_repeat", "background-repeat"),
-        ("BackgroundSize", "background_size", "background-size"),
-        ("Border", "border", "border"),
-        ("BorderBottom", "border_bottom", "border-bottom"),
-        ("BorderBottomColor", "border_bottom_color", "border-bottom-color"),
-        ("BorderBottomLeftRadius", "border_bottom_left_radius", "border-bottom-left-radius"),
-        ("BorderBottomRightRadius", "border_bottom_right_radius", "border-bottom-right-radius"),
-        ("BorderBottomStyle", "border_bottom_style", "border-bottom-style"),
-        ("BorderBottomWidth", "border_bottom_width", "border-bottom-width"),
-        ("BorderCollapse", "border_collapse", "border-collapse"),
-        ("BorderColor", "border_color", "border-color"),
-        ("BorderImage", "border_image", "border-image"),
-        ("BorderImageOutset", "border_image_outset", "border-image-outset"),
-        ("BorderImageRepeat", "border_image_repeat", "border-image-repeat"),
-        ("BorderImageSlice", "border_image_slice", "border-image-slice"),
-        ("BorderImageSource", "border_image_source", "border-image-source"),
-        ("BorderImageWidth", "border_image_width", "border-image-width"),
-        ("BorderLeft", "border_left", "border-left"),
-        ("BorderLeftColor", "border_left_color", "border-left-color"),
-        ("BorderLeftStyle", "border_left_style", "border-left-style"),
-        ("BorderLeftWidth", "border_left_width", "border-left-width"),
-        ("BorderRadius", "border_radius", "border-radius"),
-        ("BorderRight", "border_right", "border-right"),
-        ("BorderRightColor", "border_right_color", "border-right-color"),
-        ("BorderRightStyle", "border_right_style", "border-right-style"),
-        ("BorderRightWidth", "border_right_width", "border-right-width"),
-        ("BorderSpacing", "border_spacing", "border-spacing"),
-        ("BorderStyle", "border_style", "border-style"),
-        ("BorderTop", "border_top", "border-top"),
-        ("BorderTopColor", "border_top_color", "border-top-color"),
-        ("BorderTopLeftRadius", "border_top_left_radius", "border-top-left-radius"),
-        ("BorderTopRightRadius", "border_top_right_radius", "border-top-right-radius"),
-        ("BorderTopStyle", "border_top_style", "border-top-style"),
-        ("BorderTopWidth", "border_top_width", "border-top-width"),
-        ("BorderWidth", "border_width", "border-width"),
-        ("Bottom", "bottom", "bottom"),
-        ("BoxShadow", "box_shadow", "box-shadow"),
-        ("BoxSizing", "box_sizing", "box-sizing"),
-        ("CaptionSide", "caption_side", "caption-side"),
-        ("CaretColor", "caret_color", "caret-color"),
-        ("Clear", "clear", "clear"),
-        ("Clip", "clip", "clip"),
-        ("ClipPath", "clip_path", "clip-path"),
-        ("Color", "color", "color"),
-        ("ColumnCount", "column_count", "column-count"),
-        ("ColumnFill", "column_fill", "column-fill"),
-        ("ColumnGap", "column_gap", "column-gap"),
-        ("ColumnRule", "column_rule", "column-rule"),
-        ("ColumnRuleColor", "column_rule_color", "column-rule-color"),
-        ("ColumnRuleStyle", "column_rule_style", "column-rule-style"),
-        ("ColumnRuleWidth", "column_rule_width", "column-rule-width"),
-        ("ColumnSpan", "column_span", "column-span"),
-        ("ColumnWidth", "column_width", "column-width"),
-        ("Columns", "columns", "columns"),
-        ("Content", "content", "content"),
-        ("CounterIncrement", "counter_increment", "counter-increment"),
-        ("CounterReset", "counter_reset", "counter-reset"),
-        ("Cursor", "cursor", "cursor"),
-        ("DirectionLevel", "direction_level", "direction-level"),
-        ("Display", "display", "display"),
-        ("EmptyCells", "empty_cells", "empty-cells"),
-        ("Filter", "filter", "filter"),
-        ("Flex", "flex", "flex"),
-        ("FlexBasis", "flex_basis", "flex-basis"),
-        ("FlexDirection", "flex_direction", "flex-direction"),
-        ("FlexFlow", "flex_flow", "flex-flow"),
-        ("FlexGrow", "flex_grow", "flex-grow"),
-        ("FlexShrink", "flex_shrink", "flex-shrink"),
-        ("FlexWrap", "flex_wrap", "flex-wrap"),
-        ("Float", "float", "float"),
-        ("Font", "font", "font"),
-        ("FontFamily", "font_family", "font-family"),
-        ("FontKerning", "font_kerning", "font-kerning"),
-        ("FontSize", "font_size", "font-size"),
-        ("FontSizeAdjustBack", "font_size_adjust_back", "font-size-adjust-back"),
-        ("FontStretch", "font_stretch", "font-stretch"),
-        ("FontStyle", "font_style", "font-style"),
-        ("FontVariantCaps", "font_variant_caps", "font-variant-caps"),
-        ("FontWeight", "font_weight", "font-weight"),
-        ("Grid", "grid", "grid"),
-        ("GridArea", "grid_area", "grid-area"),
-        ("GridAutoColumns", "grid_auto_columns", "grid-auto-columns"),
-        ("GridAutoFlow", "grid_auto_flow", "grid-auto-flow"),
-        ("GridAutoRows", "grid_auto_rows", "grid-auto-rows"),
-        ("GridColumn", "grid_column", "grid-column"),
-        ("GridColumnEndLine", "grid_column_end_line", "grid-column-end-line"),
-        ("GridColumnGap", "grid_column_gap", "grid-column-gap"),
-        ("GridColumnStart", "grid_column_start", "grid-column-start"),
-        ("GridGap", "grid_gap", "grid-gap"),
-        ("GridRow", "grid_row", "grid-row"),
-        ("GridRowEndLine", "grid_row_end_line", "grid-row-end-line"),
-        ("GridRowGap", "grid_row_gap", "grid-row-gap"),
-        ("GridRowStart", "grid_row_start", "grid-row-start"),
-        ("GridTemplate", "grid_template", "grid-template"),
-        ("GridTemplateAreas", "grid_template_areas", "grid-template-areas"),
-        ("GridTemplateColumns", "grid_template_columns", "grid-template-columns"),
-        ("GridTemplateRows", "grid_template_rows", "grid-template-rows"),
-        ("Height", "height", "height"),
-        ("Hyphens", "hyphens", "hyphens"),
-        ("JustifyContent", "justify_content", "justify-content"),
-        ("Left", "left", "left"),
-        ("LetterSpacing", "letter_spacing", "letter-spacing"),
-        ("LineHeight", "line_height", "line-height"),
-        ("ListStyle", "list_style", "list-style"),
-        ("ListStyleImage", "list_style_image", "list-style-image"),
-        ("ListStylePosition", "list_style_position", "list-style-position"),
-        ("ListStyleType", "list_style_type", "list-style-type"),
-        ("Margin", "margin", "margin"),
-        ("MarginBottom", "margin_bottom", "margin-bottom"),
-        ("MarginLeft", "margin_left", "margin-left"),
-        ("MarginRight", "margin_right", "margin-right"),
-        ("MarginTop", "margin_top", "margin-top"),
-        ("MaxHeight", "max_height", "max-height"),
-        ("MaxWidth", "max_width", "max-width"),
-        ("MinHeight", "min_height", "min-height"),
-        ("MinWidth", "min_width", "min-width"),
-        ("ObjectFit", "object_fit", "object-fit"),
-        ("ObjectPosition", "object_position", "object-position"),
-        ("Opacity", "opacity", "opacity"),
-        ("Order", "order", "order"),
-        ("Outline", "outline", "outline"),
-        ("OutlineColor", "outline_color", "outline-color"),
-        ("OutlineOffset", "outline_offset", "outline-offset"),
-        ("OutlineStyle", "outline_style", "outline-style"),
-        ("OutlineWidth", "outline_width", "outline-width"),
-        ("Overflow", "overflow", "overflow"),
-        ("OverflowX", "overflow_x", "overflow-x"),
-        ("OverflowY", "overflow_y", "overflow-y"),
-        ("Padding", "padding", "padding"),
-        ("PaddingBottom", "padding_bottom", "padding-bottom"),
-        ("PaddingLeft", "padding_left", "padding-left"),
-        ("PaddingRight", "padding_right", "padding-right"),
-        ("PaddingTop", "padding_top", "padding-top"),
-        ("PageBreakAfterBreak", "page_break_after_break", "page-break-after-break"),
-        ("PageBreakBeforeBreak", "page_break_before_break", "page-break-before-break"),
-        ("PageBreakInsideBreak", "page_break_inside_break", "page-break-inside-break"),
-        ("PerspectivePositioned", "perspective_positioned", "perspective-positioned"),
-        ("PerspectiveOriginPositioned", "perspective_origin_positioned", "perspective-origin-positioned"),
-        ("PointerEvents", "pointer_events", "pointer-events"),
-        ("Position", "position", "position"),
-        ("Quotes", "quotes", "quotes"),
-        ("Right", "right", "right"),
-        ("ScrollBehavior", "scroll_behavior", "scroll-behavior"),
-        ("TableLayout", "table_layout", "table-layout"),
-        ("TextAlign", "text_align", "text-align"),
-        ("TextAlignLast", "text_align_last", "text-align-last"),
-        ("TextDecoration", "text_decoration", "text-decoration"),
-        ("TextDecorationColor", "text_decoration_color", "text-decoration-color"),
-        ("TextDecorationLine", "text_decoration_line", "text-decoration-line"),
-        ("TextDecorationStyle", "text_decoration_style", "text-decoration-style"),
-        ("TextIndent", "text_indent", "text-indent"),
-        ("TextJustify", "text_justify", "text-justify"),
-        ("TextOverflow", "text_overflow", "text-overflow"),
-        ("TextShadow", "text_shadow", "text-shadow"),
-        ("TextTransform", "text_transform", "text-transform"),
-        ("Top", "top", "top"),
-        ("Transform", "transform", "transform"),
-        ("TransformOrigin", "transform_origin", "transform-origin"),
-        ("TransformStyle", "transform_style", "transform-style"),
-        ("Transition", "transition", "transition"),
-        ("TransitionDelay", "transition_delay", "transition-delay"),
-        ("TransitionDuration", "transition_duration", "transition-duration"),
-        ("TransitionProperty", "transition_property", "transition-property"),
-        ("TransitionTimingFunction", "transition_timing_function", "transition-timing-function"),
-        ("UserSelect", "user_select", "user-select"),
-        ("VerticalAlign", "vertical_align", "vertical-align"),
-        ("Visibility", "visibility", "visibility"),
-        ("WhiteSpaceSpace", "white_space_space", "white-space-space"),
-        ("Width", "width", "width"),
-        ("WordBreak", "word_break", "word-break"),
-        ("WordSpacing", "word_spacing", "word-spacing"),
-        ("WordWrap", "word_wrap", "word-wrap"),
-        ("WritingMode", "writing_mode", "writing-mode"),
-        ("ZIndex", "z_index", "z-index"),
-    ];
-
     let mut code = String::new();
 
-    for (rust_name, function_name, html_name) in idents {
-        code.push_str(&format!("css_prop!({}, {}, {});", rust_name, function_name, html_name));
+    let mut arms = String::new();
+
+    for entry in spec_entries(CSS_PROPERTIES) {
+        let css_name = entry[0];
+        code.push_str(&format!("css_prop!({}, {}, {});", rust_name(css_name), function_name(css_name), css_name));
+        arms.push_str(&format!("\"{}\" => Some(Box::new({}(value))),", css_name, function_name(css_name)));
     }
 
+    // Lookup from the name of a property to the generated struct, for parsing.
+    code.push_str(&format!(
+        "pub(crate) fn prop_from_name(name : &str, value : &str) -> Option<Box<dyn CssProp>> {{ match name {{ {} _ => None }} }}",
+        arms
+    ));
+
     code.parse().unwrap()
 }
 
+// FNV-1a, so that scoped class names only change when the stylesheet does.
+fn hash(string : &str) -> u64 {
+    string.bytes().fold(0xcbf29ce484222325, |hash, byte| (hash ^ byte as u64).wrapping_mul(0x100000001b3))
+}
 
+fn is_name_start(c : char) -> bool {
+    c.is_ascii_alphabetic() || c == '_' || c == '-' || !c.is_ascii()
+}
+
+fn is_name_char(c : char) -> bool {
+    is_name_start(c) || c.is_ascii_digit()
+}
+
+// Copies a string or comment starting at `chars[start]` to `output`, returning the index after it.
+fn copy_literal(chars : &[char], start : usize, output : &mut String) -> usize {
+    let end = if chars[start] == '/' {
+        // Comment, ending at the first `*/` after its opening `/*`.
+        let mut index = start + 2;
+        while index + 1 < chars.len() && !(chars[index] == '*' && chars[index + 1] == '/') {
+            index += 1;
+        }
+        index + 2
+    }
+    else {
+        let quote = chars[start];
+        let mut index = start + 1;
+        while index < chars.len() && chars[index] != quote {
+            if chars[index] == '\\' {
+                index += 1;
+            }
+            index += 1;
+        }
+        index + 1
+    };
+
+    let end = end.min(chars.len());
+    output.extend(&chars[start..end]);
+    end
+}
+
+fn is_literal_start(chars : &[char], index : usize) -> bool {
+    chars[index] == '"' || chars[index] == '\'' || (chars[index] == '/' && chars.get(index + 1) == Some(&'*'))
+}
+
+// Renames every class in a selector to `{class}_{suffix}`, recording the original names.
+fn scope_selector(selector : &str, suffix : &str, classes : &mut Vec<String>) -> String {
+    let chars : Vec<char> = selector.chars().collect();
+    let mut scoped = String::new();
+    let mut index = 0;
+    while index < chars.len() {
+        if is_literal_start(&chars, index) {
+            index = copy_literal(&chars, index, &mut scoped);
+        }
+        else if chars[index] == '[' {
+            while index < chars.len() && chars[index] != ']' {
+                scoped.push(chars[index]);
+                index += 1;
+            }
+        }
+        else if chars[index] == '.' && chars.get(index + 1).is_some_and(|c| is_name_start(*c)) {
+            let start = index + 1;
+            index = start;
+            while index < chars.len() && is_name_char(chars[index]) {
+                index += 1;
+            }
+            let class : String = chars[start..index].iter().collect();
+            scoped.push_str(&format!(".{}_{}", class, suffix));
+            if !classes.contains(&class) {
+                classes.push(class);
+            }
+        }
+        else {
+            scoped.push(chars[index]);
+            index += 1;
+        }
+    }
+    scoped
+}
+
+// Scopes the class selectors of a stylesheet. Only the text before a `{` can be a selector, so
+// declarations, which end in `;` or `}`, and at-rule preludes are left alone.
+fn scope_stylesheet(css : &str, suffix : &str) -> (String, Vec<String>) {
+    let chars : Vec<char> = css.chars().collect();
+    let mut scoped = String::new();
+    let mut classes = Vec::new();
+    let mut statement = String::new();
+    let mut index = 0;
+    while index < chars.len() {
+        match chars[index] {
+            _ if is_literal_start(&chars, index) => {
+                index = copy_literal(&chars, index, &mut statement);
+                continue;
+            },
+            '{' => {
+                if statement.trim_start().starts_with('@') {
+                    scoped.push_str(&statement);
+                }
+                else {
+                    scoped.push_str(&scope_selector(&statement, suffix, &mut classes));
+                }
+                scoped.push('{');
+                statement.clear();
+            },
+            ';' | '}' => {
+                scoped.push_str(&statement);
+                scoped.push(chars[index]);
+                statement.clear();
+            },
+            c => statement.push(c),
+        }
+        index += 1;
+    }
+    scoped.push_str(&statement);
+
+    (scoped, classes)
+}
+
+// `card-title` becomes `CARD_TITLE`.
+fn constant_name(class : &str) -> String {
+    let name : String = class.chars().map(|c| if c.is_alphanumeric() { c.to_ascii_uppercase() } else { '_' }).collect();
+    if name.starts_with(|c : char| c.is_ascii_digit()) {
+        format!("_{}", name)
+    }
+    else {
+        name
+    }
+}
+
+// Module of a class name constant for each class in the stylesheet, scoped with a hash of the
+// module name and stylesheet, and a `css` function for the scoped stylesheet itself.
+fn css_module_code(module : &Ident, css : &str, path : Option<String>) -> proc_macro2::TokenStream {
+    let suffix = format!("{:06x}", hash(&format!("{}{}", module, css)) & 0xffffff);
+    let (scoped, classes) = scope_stylesheet(css, &suffix);
+
+    let constants = classes.iter().map(|class| {
+        let constant = format_ident!("{}", constant_name(class));
+        let scoped_class = format!("{}_{}", class, suffix);
+        quote! {
+            pub const #constant : ::dsl::attr::ClassName = ::dsl::attr::ClassName(#scoped_class);
+        }
+    });
+
+    // Rebuilds the module when the file changes.
+    let dependency = path.map(|path| quote! {
+        const _ : &[u8] = include_bytes!(#path);
+    });
+
+    quote! {
+        pub mod #module {
+            #(#constants)*
+
+            pub fn css() -> &'static str {
+                #scoped
+            }
+
+            #dependency
+        }
+    }
+}
+
+struct CssModule {
+    module : Ident,
+    css : LitStr,
+}
+
+impl Parse for CssModule {
+    fn parse(input : ParseStream) -> syn::Result<Self> {
+        let module = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let css = input.parse()?;
+        Ok(CssModule { module, css })
+    }
+}
+
+// `css_module!(card, ".card { ... }")` declares the module `card`.
+#[proc_macro]
+pub fn css_module(input : TokenStream) -> TokenStream {
+    let CssModule { module, css } = parse_macro_input!(input as CssModule);
+    css_module_code(&module, &css.value(), None).into()
+}
+
+// `include_css!("styles/card.css")` declares the module `card`, with the path relative to the
+// crate root.
+#[proc_macro]
+pub fn include_css(input : TokenStream) -> TokenStream {
+    let relative = parse_macro_input!(input as LitStr);
+
+    let mut path = std::path::PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").unwrap_or_default());
+    path.push(relative.value());
+
+    let css = match std::fs::read_to_string(&path) {
+        Ok(css) => css,
+        Err(error) => {
+            let message = format!("could not read `{}`: {}", path.display(), error);
+            return syn::Error::new(relative.span(), message).to_compile_error().into();
+        },
+    };
+
+    let stem = path.file_stem().map(|stem| stem.to_string_lossy().to_string()).unwrap_or_default();
+    let module = format_ident!("{}", stem.replace(|c : char| !c.is_alphanumeric(), "_").to_lowercase());
+
+    css_module_code(&module, &css, Some(path.display().to_string())).into()
+}
+
+// Source of known class names, being either a class name, or `include "path.css"` for every class
+// used in the selectors of a stylesheet.
+enum KnownClasses {
+    Class(LitStr),
+    Include(LitStr),
+}
+
+impl Parse for KnownClasses {
+    fn parse(input : ParseStream) -> syn::Result<Self> {
+        if input.peek(LitStr) {
+            return Ok(KnownClasses::Class(input.parse()?));
+        }
+
+        let keyword : Ident = input.parse()?;
+        if keyword != "include" {
+            return Err(syn::Error::new(keyword.span(), "expected a class name or `include \"path.css\"`"));
+        }
+        Ok(KnownClasses::Include(input.parse()?))
+    }
+}
+
+// Declares the `class!` macro, which only accepts known class names, so that
+// `class!("btn", "btn-primary")` is `attr::class("btn btn-primary")` and `class!("btn-primray")`
+// fails to compile.
+#[proc_macro]
+pub fn known_classes(input : TokenStream) -> TokenStream {
+    let sources = parse_macro_input!(input with syn::punctuated::Punctuated::<KnownClasses, Token![,]>::parse_terminated);
+
+    let mut classes : Vec<String> = Vec::new();
+    let mut dependencies = Vec::new();
+    for source in sources {
+        match source {
+            KnownClasses::Class(class) => classes.push(class.value()),
+            KnownClasses::Include(relative) => {
+                let mut path = std::path::PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").unwrap_or_default());
+                path.push(relative.value());
+
+                match std::fs::read_to_string(&path) {
+                    Ok(css) => classes.extend(scope_stylesheet(&css, "").1),
+                    Err(error) => {
+                        let message = format!("could not read `{}`: {}", path.display(), error);
+                        return syn::Error::new(relative.span(), message).to_compile_error().into();
+                    },
+                }
+
+                let path = path.display().to_string();
+                dependencies.push(quote! {
+                    const _ : &[u8] = include_bytes!(#path);
+                });
+            },
+        }
+    }
+    classes.sort();
+    classes.dedup();
+
+    let code = quote! {
+        #(#dependencies)*
+
+        macro_rules! class {
+            #((@known #classes) => { #classes };)*
+            (@known $class:tt) => {
+                compile_error!(concat!("unknown class `", $class, "`"))
+            };
+            // Classes are forwarded as token trees, as a `literal` fragment can't match the literals
+            // of the arms above.
+            ($($class:tt),+ $(,)?) => {
+                ::dsl::attr::class(&[$(class!(@known $class)),+].join(" "))
+            };
+        }
+    };
+
+    code.into()
+}
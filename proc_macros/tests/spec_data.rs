@@ -0,0 +1,173 @@
+// Checks the vendored spec data which `all_nodes!`, `all_attributes!` and `all_css_props!` are
+// generated from. The data files are copied from the WHATWG element and attribute indexes and the
+// W3C CSS property index, and the macros generate nothing else, so these tests check that the
+// copies are well formed, that names known to be bogus stay out and that names known to be real
+// stay in, and that the generated CSS properties are exactly the vendored ones. They can't tell a
+// misspelling in the data itself from a real name.
+
+const HTML_ELEMENTS : &str = include_str!("../spec/html-elements.txt");
+const HTML_ATTRIBUTES : &str = include_str!("../spec/html-attributes.txt");
+const CSS_PROPERTIES : &str = include_str!("../spec/css-properties.txt");
+
+fn entries(data : &str) -> Vec<Vec<&str>> {
+    data
+    .lines()
+    .map(|line| line.trim())
+    .filter(|line| !line.is_empty() && !line.starts_with('#'))
+    .map(|line| line.split_whitespace().collect())
+    .collect()
+}
+
+fn names(data : &str) -> Vec<&str> {
+    entries(data).into_iter().map(|entry| entry[0]).collect()
+}
+
+fn assert_well_formed(names : &[&str]) {
+    for name in names {
+        assert!(
+            name.starts_with(|c : char| c.is_ascii_lowercase())
+                && name.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+                && !name.ends_with('-')
+                && !name.contains("--"),
+            "`{}` is not a valid spec name", name
+        );
+    }
+
+    let mut sorted = names.to_vec();
+    sorted.sort_unstable();
+    sorted.dedup();
+    assert_eq!(sorted.len(), names.len(), "spec data contains duplicate names");
+}
+
+#[test]
+fn elements_are_real() {
+    for entry in entries(HTML_ELEMENTS) {
+        assert_eq!(entry.len(), 2, "`{}` should have a name and a kind", entry.join(" "));
+        assert!(entry[1] == "normal" || entry[1] == "void", "`{}` has an unknown kind", entry[0]);
+    }
+
+    let elements = names(HTML_ELEMENTS);
+    assert_well_formed(&elements);
+
+    for required in ["search", "hgroup", "menu", "slot", "style"] {
+        assert!(elements.contains(&required), "missing element `{}`", required);
+    }
+}
+
+#[test]
+fn attributes_are_real() {
+    let attributes = names(HTML_ATTRIBUTES);
+    assert_well_formed(&attributes);
+
+    for removed in ["onmousewheel", "onsearch"] {
+        assert!(!attributes.contains(&removed), "`{}` is not a standard attribute", removed);
+    }
+}
+
+#[test]
+fn css_properties_are_real() {
+    let properties = names(CSS_PROPERTIES);
+    assert_well_formed(&properties);
+
+    let bogus = [
+        "direction-level",
+        "font-size-adjust-back",
+        "white-space-space",
+        "page-break-after-break",
+        "page-break-before-break",
+        "page-break-inside-break",
+        "grid-row-end-line",
+        "grid-column-end-line",
+        "perspective-positioned",
+        "perspective-origin-positioned",
+    ];
+    for name in bogus {
+        assert!(!properties.contains(&name), "`{}` is not a CSS property", name);
+    }
+
+    let required = [
+        "gap",
+        "row-gap",
+        "aspect-ratio",
+        "inset",
+        "white-space",
+        "direction",
+        "font-variant",
+        "transition-behavior",
+    ];
+    for name in required {
+        assert!(properties.contains(&name), "missing CSS property `{}`", name);
+    }
+}
+
+#[test]
+fn modern_css_properties_are_covered() {
+    let properties = names(CSS_PROPERTIES);
+
+    let required = [
+        "margin-inline",
+        "padding-block",
+        "inset-inline-start",
+        "place-items",
+        "container-type",
+        "container-name",
+        "scroll-snap-type",
+        "scroll-snap-align",
+        "overscroll-behavior",
+        "accent-color",
+        "backdrop-filter",
+        "mask-image",
+        "content-visibility",
+        "touch-action",
+        "will-change",
+    ];
+    for name in required {
+        assert!(properties.contains(&name), "missing CSS property `{}`", name);
+    }
+}
+
+// Just enough of `dsl::css` for the generated properties to compile against. The generated structs
+// implement `ToString` directly, as `dsl` does.
+#[allow(clippy::to_string_trait_impl)]
+mod css {
+    use proc_macros::*;
+
+    pub trait CssValue : ToString {
+        fn responsive(&self) -> Vec<(String, String)> {
+            Vec::new()
+        }
+    }
+    impl CssValue for &str {}
+
+    pub trait CssProp : ToString {
+        fn name(&self) -> String;
+
+        fn value(&self) -> String;
+
+        fn responsive(&self) -> Vec<(String, String)> {
+            Vec::new()
+        }
+    }
+
+    fn remove_whitespace(string : &str) -> String {
+        string.chars().filter(|c| !c.is_whitespace()).collect()
+    }
+
+    all_css_props!();
+
+    pub fn lookup(name : &str, value : &str) -> Option<Box<dyn CssProp>> {
+        prop_from_name(name, value)
+    }
+}
+
+#[test]
+fn generated_css_properties_match_spec_data() {
+    for name in names(CSS_PROPERTIES) {
+        let prop = css::lookup(name, "x").unwrap_or_else(|| panic!("no property generated for `{}`", name));
+        assert_eq!(prop.name(), name);
+        assert_eq!(prop.value(), "x");
+        assert!(prop.responsive().is_empty());
+        assert_eq!(prop.to_string(), format!("{}: x;", name));
+    }
+    assert!(css::lookup("direction-level", "x").is_none());
+}